@@ -0,0 +1,287 @@
+use crate::parse_pair;
+use aoc_common::{error::ParseError, parse::read_lines};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+};
+use tempfile::TempDir;
+
+// Ids kept in memory per column before a sorted run is spilled to disk
+pub const DEFAULT_RUN_LEN: usize = 1 << 20;
+
+// Most runs merged at once, every one of them keeps a file open during the merge
+pub const MAX_FAN_IN: usize = 64;
+
+// Both location lists sorted in runs on disk, for inputs larger than memory
+//
+// Only `run_len` ids per column are held in memory while reading, the answers are
+// computed by k-way merging the runs back in ascending order. Runs beyond `MAX_FAN_IN`
+// per column are first merged into longer ones, so no more than twice that many files
+// are ever open at once.
+pub struct SpilledLists {
+    // Removes the run files when dropped
+    _dir: TempDir,
+    left: Vec<PathBuf>,
+    right: Vec<PathBuf>,
+}
+
+// One column being read, sorted and written out a run at a time
+struct Column {
+    name: &'static str,
+    run_len: usize,
+    buffer: Vec<i32>,
+    runs: Vec<PathBuf>,
+    // Run files created so far, for unique names
+    created: usize,
+}
+
+impl Column {
+    fn new(name: &'static str, run_len: usize) -> Column {
+        Column {
+            name,
+            run_len,
+            buffer: Vec::with_capacity(run_len),
+            runs: Vec::new(),
+            created: 0,
+        }
+    }
+
+    fn push(&mut self, id: i32, dir: &TempDir) -> io::Result<()> {
+        self.buffer.push(id);
+        if self.buffer.len() >= self.run_len {
+            self.spill(dir)?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self, dir: &TempDir) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.buffer.sort_unstable();
+        let (path, mut out) = self.create(dir)?;
+        for id in &self.buffer {
+            out.write_all(&id.to_le_bytes())?;
+        }
+        out.flush()?;
+        self.runs.push(path);
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn create(&mut self, dir: &TempDir) -> io::Result<(PathBuf, BufWriter<File>)> {
+        let path = dir.path().join(format!("{}-{}", self.name, self.created));
+        self.created += 1;
+        let out = BufWriter::new(File::create(&path)?);
+        Ok((path, out))
+    }
+
+    // Merge runs in groups of `fan_in` until no more than `fan_in` are left
+    fn reduce(&mut self, dir: &TempDir, fan_in: usize) -> io::Result<()> {
+        while self.runs.len() > fan_in {
+            for group in std::mem::take(&mut self.runs).chunks(fan_in) {
+                if let [run] = group {
+                    self.runs.push(run.clone());
+                    continue;
+                }
+                let (path, mut out) = self.create(dir)?;
+                for id in Merge::open(group)? {
+                    out.write_all(&id?.to_le_bytes())?;
+                }
+                out.flush()?;
+                for run in group {
+                    fs::remove_file(run)?;
+                }
+                self.runs.push(path);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SpilledLists {
+    // Read the lists, spilling every `run_len` ids of a column as one sorted run
+    pub fn read(reader: impl BufRead, run_len: usize) -> Result<SpilledLists, ParseError> {
+        SpilledLists::read_with_fan_in(reader, run_len, MAX_FAN_IN)
+    }
+
+    fn read_with_fan_in(
+        reader: impl BufRead,
+        run_len: usize,
+        fan_in: usize,
+    ) -> Result<SpilledLists, ParseError> {
+        let io_error = |number, why: io::Error| {
+            ParseError::new(number, 1, "", format!("couldn't spill sorted run: {}", why))
+        };
+        let dir = TempDir::new().map_err(|why| io_error(1, why))?;
+        let mut left = Column::new("left", run_len.max(1));
+        let mut right = Column::new("right", run_len.max(1));
+        let number = read_lines(reader, |line| {
+            let (a, b) = parse_pair(line)?;
+            left.push(a, &dir)
+                .map_err(|why| io_error(line.number, why))?;
+            right
+                .push(b, &dir)
+                .map_err(|why| io_error(line.number, why))
+        })?;
+        left.spill(&dir).map_err(|why| io_error(number, why))?;
+        right.spill(&dir).map_err(|why| io_error(number, why))?;
+        for column in [&mut left, &mut right] {
+            column
+                .reduce(&dir, fan_in.max(2))
+                .map_err(|why| io_error(number, why))?;
+        }
+        Ok(SpilledLists {
+            _dir: dir,
+            left: left.runs,
+            right: right.runs,
+        })
+    }
+
+    // Number of sorted runs per column left for the final merge
+    pub fn runs(&self) -> usize {
+        self.left.len()
+    }
+
+    // Part 1 on the merged columns, the k-th smallest ids are paired up
+    pub fn total_distance(&self) -> io::Result<i64> {
+        let mut sum = 0;
+        for (a, b) in Merge::open(&self.left)?.zip(Merge::open(&self.right)?) {
+            sum += (a? as i64 - b? as i64).abs();
+        }
+        Ok(sum)
+    }
+
+    // Part 2 as a merge join, both columns come back sorted
+    pub fn similarity_score(&self) -> io::Result<i64> {
+        let mut right = Merge::open(&self.right)?;
+        let mut next = right.next().transpose()?;
+        // The last left id and how often it appears in the right column
+        let mut last: Option<(i32, i64)> = None;
+        let mut sum = 0;
+        for a in Merge::open(&self.left)? {
+            let a = a?;
+            let count = match last {
+                Some((id, count)) if id == a => count,
+                _ => {
+                    let mut count = 0;
+                    while let Some(b) = next.filter(|&b| b <= a) {
+                        if b == a {
+                            count += 1;
+                        }
+                        next = right.next().transpose()?;
+                    }
+                    last = Some((a, count));
+                    count
+                }
+            };
+            sum += a as i64 * count;
+        }
+        Ok(sum)
+    }
+}
+
+// K-way merge of sorted runs, yielding all ids in ascending order
+struct Merge {
+    readers: Vec<BufReader<File>>,
+    // Smallest unread id of every run that is not exhausted, tagged with the run
+    heads: BinaryHeap<Reverse<(i32, usize)>>,
+}
+
+impl Merge {
+    fn open(runs: &[PathBuf]) -> io::Result<Merge> {
+        let mut merge = Merge {
+            readers: Vec::with_capacity(runs.len()),
+            heads: BinaryHeap::with_capacity(runs.len()),
+        };
+        for (run, path) in runs.iter().enumerate() {
+            merge.readers.push(BufReader::new(File::open(path)?));
+            merge.advance(run)?;
+        }
+        Ok(merge)
+    }
+
+    fn advance(&mut self, run: usize) -> io::Result<()> {
+        let mut bytes = [0; 4];
+        match self.readers[run].read_exact(&mut bytes) {
+            Ok(()) => self.heads.push(Reverse((i32::from_le_bytes(bytes), run))),
+            Err(why) if why.kind() == io::ErrorKind::UnexpectedEof => {}
+            Err(why) => return Err(why),
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for Merge {
+    type Item = io::Result<i32>;
+
+    fn next(&mut self) -> Option<io::Result<i32>> {
+        let Reverse((id, run)) = self.heads.pop()?;
+        Some(self.advance(run).map(|_| id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day01;
+    use aoc_common::day::Solution;
+
+    #[test]
+    fn test_example() {
+        let input = include_str!("../example.txt");
+        let lists = SpilledLists::read(input.as_bytes(), 2).unwrap();
+        assert_eq!(lists.runs(), 3);
+        assert_eq!(lists.total_distance().unwrap(), 11);
+        assert_eq!(lists.similarity_score().unwrap(), 31);
+    }
+
+    #[test]
+    fn test_matches_in_memory() {
+        let input: String = (0..1000)
+            .map(|i| format!("{}   {}\n", i * 7919 % 101, i * 104729 % 89))
+            .collect();
+        let expected = Day01::parse(&input).unwrap();
+        for run_len in [1, 7, 64, 1000, DEFAULT_RUN_LEN] {
+            let lists = SpilledLists::read(input.as_bytes(), run_len).unwrap();
+            assert_eq!(lists.total_distance().unwrap(), Day01::part1(&expected));
+            assert_eq!(lists.similarity_score().unwrap(), Day01::part2(&expected));
+        }
+    }
+
+    #[test]
+    fn test_bounded_fan_in() {
+        let input: String = (0..500)
+            .map(|i| format!("{}   {}\n", i * 7919 % 101, i * 104729 % 89))
+            .collect();
+        let expected = Day01::parse(&input).unwrap();
+        for fan_in in [2, 3, 64] {
+            let lists = SpilledLists::read_with_fan_in(input.as_bytes(), 1, fan_in).unwrap();
+            assert!(lists.runs() <= fan_in);
+            // Merged runs are removed, only those left for the final merge remain
+            let files = fs::read_dir(lists._dir.path()).unwrap().count();
+            assert_eq!(files, 2 * lists.runs());
+            assert_eq!(lists.total_distance().unwrap(), Day01::part1(&expected));
+            assert_eq!(lists.similarity_score().unwrap(), Day01::part2(&expected));
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = SpilledLists::read("1 2\n3 x\n".as_bytes(), 1)
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "x"));
+    }
+
+    #[test]
+    fn test_empty() {
+        let lists = SpilledLists::read("".as_bytes(), 4).unwrap();
+        assert_eq!(lists.runs(), 0);
+        assert_eq!(lists.total_distance().unwrap(), 0);
+        assert_eq!(lists.similarity_score().unwrap(), 0);
+    }
+}
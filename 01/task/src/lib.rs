@@ -0,0 +1,341 @@
+use aoc_common::{
+    day::{Day, Example, Part, Solution},
+    error::ParseError,
+    parse::{lines, read_lines, Line},
+};
+use std::{collections::HashMap, io::BufRead, str::FromStr};
+
+pub mod external;
+
+pub const DAY: Day = Day::new::<Day01>(aoc_common::default_input!());
+
+pub struct Day01;
+
+// The location lists side by side, each column in input order
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Lists {
+    columns: Vec<Vec<i32>>,
+}
+
+// How far apart the k-th smallest ids of the columns are
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    // Sum of the absolute differences between neighbouring columns
+    Absolute,
+    // Sum of the squared differences between neighbouring columns
+    Squared,
+    // Largest minus smallest id
+    MaxSpread,
+}
+
+impl Metric {
+    pub fn distance(&self, ids: &[i32]) -> i64 {
+        let steps = ids.windows(2).map(|w| w[1] as i64 - w[0] as i64);
+        match self {
+            Metric::Absolute => steps.map(|d| d.abs()).sum(),
+            Metric::Squared => steps.map(|d| d * d).sum(),
+            Metric::MaxSpread => {
+                let max = ids.iter().max().copied().unwrap_or(0) as i64;
+                let min = ids.iter().min().copied().unwrap_or(0) as i64;
+                max - min
+            }
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "absolute" => Ok(Metric::Absolute),
+            "squared" => Ok(Metric::Squared),
+            "max-spread" => Ok(Metric::MaxSpread),
+            _ => Err(format!(
+                "unknown metric `{}`, expected absolute, squared or max-spread",
+                s
+            )),
+        }
+    }
+}
+
+// The k-th smallest id of every column and what they add to the total
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pairing {
+    // 0-based rank in the sorted columns
+    pub rank: usize,
+    pub ids: Vec<i32>,
+    pub distance: i64,
+}
+
+impl Lists {
+    // Read the lists line by line, so huge inputs never have to be held as text
+    //
+    // Any number of columns from 2 up is accepted, as long as every line has the same.
+    pub fn read(reader: impl BufRead) -> Result<Lists, ParseError> {
+        let mut lists = Lists::default();
+        read_lines(reader, |line| lists.push(line, None))?;
+        Ok(lists)
+    }
+
+    // Add a line, which has to have `columns` ids or as many as the lines before
+    fn push(&mut self, line: &Line, columns: Option<usize>) -> Result<(), ParseError> {
+        let expected = columns.or((!self.columns.is_empty()).then_some(self.columns.len()));
+        let row = parse_row(line, expected)?;
+        if self.columns.is_empty() {
+            self.columns = vec![Vec::new(); row.len()];
+        }
+        for (column, id) in self.columns.iter_mut().zip(row) {
+            column.push(id);
+        }
+        Ok(())
+    }
+
+    pub fn columns(&self) -> &[Vec<i32>] {
+        &self.columns
+    }
+
+    // Pair up the k-th smallest ids of all columns
+    pub fn pairings(&self, metric: Metric) -> Vec<Pairing> {
+        let sorted: Vec<Vec<i32>> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let mut column = column.clone();
+                column.sort_unstable();
+                column
+            })
+            .collect();
+        let len = sorted.first().map_or(0, |column| column.len());
+        (0..len)
+            .map(|rank| {
+                let ids: Vec<i32> = sorted.iter().map(|column| column[rank]).collect();
+                let distance = metric.distance(&ids);
+                Pairing {
+                    rank,
+                    ids,
+                    distance,
+                }
+            })
+            .collect()
+    }
+
+    pub fn total_distance(&self, metric: Metric) -> i64 {
+        self.pairings(metric).iter().map(|p| p.distance).sum()
+    }
+
+    // Table of every pairing and its share of the total
+    pub fn report(&self, metric: Metric) -> String {
+        let pairings = self.pairings(metric);
+        let total: i64 = pairings.iter().map(|p| p.distance).sum();
+        let ids: Vec<String> = pairings
+            .iter()
+            .map(|p| {
+                p.ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        let width = ids.iter().map(|ids| ids.len()).max().unwrap_or(0).max(3);
+
+        let mut out = format!(
+            "{:>6}  {:<width$}  {:>12}  {:>6}\n",
+            "rank", "ids", "distance", "share"
+        );
+        for (pairing, ids) in pairings.iter().zip(&ids) {
+            let share = if total == 0 {
+                0.0
+            } else {
+                pairing.distance as f64 / total as f64 * 100.0
+            };
+            out += &format!(
+                "{:>6}  {:<width$}  {:>12}  {:>5.1}%\n",
+                pairing.rank + 1,
+                ids,
+                pairing.distance,
+                share
+            );
+        }
+        out += &format!("{:>6}  {:<width$}  {:>12}\n", "total", "", total);
+        out
+    }
+}
+
+// One line of whitespace separated location ids
+//
+// With `columns` unknown, as on the first line, any number from 2 up is accepted.
+fn parse_row(line: &Line, columns: Option<usize>) -> Result<Vec<i32>, ParseError> {
+    let fields: Vec<&str> = line.text.split_whitespace().collect();
+    match columns {
+        Some(columns) if fields.len() < columns => {
+            return Err(line.error_at_end(format!("expected {} columns", columns)))
+        }
+        Some(columns) if fields.len() > columns => {
+            return Err(line.error(fields[columns], "unexpected column"))
+        }
+        None if fields.len() < 2 => return Err(line.error_at_end("expected at least 2 columns")),
+        _ => {}
+    }
+    fields.iter().map(|field| line.parse(field)).collect()
+}
+
+// One line of exactly two location ids, as in the puzzle
+fn parse_pair(line: &Line) -> Result<(i32, i32), ParseError> {
+    let row = parse_row(line, Some(2))?;
+    Ok((row[0], row[1]))
+}
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            part: Part::One,
+            input: include_str!("../example.txt"),
+            answer: "11",
+        },
+        Example {
+            part: Part::Two,
+            input: include_str!("../example.txt"),
+            answer: "31",
+        },
+    ];
+
+    type Input = Lists;
+    // Sums over millions of pairs do not fit in an i32
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Lists, ParseError> {
+        let mut lists = Lists::default();
+        for line in lines(input) {
+            lists.push(&line, Some(2))?;
+        }
+        Ok(lists)
+    }
+
+    fn part1(input: &Lists) -> i64 {
+        input.total_distance(Metric::Absolute)
+    }
+
+    // Similarity of the first column to the second
+    fn part2(input: &Lists) -> i64 {
+        // Empty input has no columns at all
+        let [a, b, ..] = &input.columns[..] else {
+            return 0;
+        };
+        // How often each id appears in the right list
+        let mut counts: HashMap<i32, i64> = HashMap::new();
+        for bv in b {
+            *counts.entry(*bv).or_insert(0) += 1;
+        }
+
+        let mut sum = 0;
+        for av in a {
+            sum += *av as i64 * counts.get(av).copied().unwrap_or(0);
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::day::check_examples;
+
+    #[test]
+    fn test_examples() {
+        check_examples::<Day01>();
+    }
+
+    #[test]
+    fn test_any_whitespace() {
+        let lists = Day01::parse("3   4\n4 3\n2\t\t5\n  1  3  \n").unwrap();
+        assert_eq!(lists.columns(), &[vec![3, 4, 2, 1], vec![4, 3, 5, 3]]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day01::parse("3   4\n4\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (2, 2, "expected 2 columns")
+        );
+        let err = Day01::parse("3   4 5\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 7, "5"));
+    }
+
+    #[test]
+    fn test_read_matches_parse() {
+        let input = include_str!("../example.txt");
+        let lists = Lists::read(input.as_bytes()).unwrap();
+        assert_eq!(lists, Day01::parse(input).unwrap());
+        assert_eq!(Day01::part2(&lists), 31);
+    }
+
+    #[test]
+    fn test_empty() {
+        for lists in [
+            Day01::parse("").unwrap(),
+            Lists::read("".as_bytes()).unwrap(),
+        ] {
+            assert_eq!(Day01::part1(&lists), 0);
+            assert_eq!(Day01::part2(&lists), 0);
+        }
+    }
+
+    #[test]
+    fn test_similarity_matches_nested_loop() {
+        let (a, b): (Vec<i32>, Vec<i32>) = (
+            (0..200).map(|i| i * 7 % 13).collect(),
+            (0..300).map(|i| i * 5 % 17).collect(),
+        );
+        let mut expected = 0;
+        for av in &a {
+            expected += *av as i64 * b.iter().filter(|bv| *bv == av).count() as i64;
+        }
+        let lists = Lists {
+            columns: vec![a, b],
+        };
+        assert_eq!(Day01::part2(&lists), expected);
+    }
+
+    #[test]
+    fn test_metrics() {
+        assert_eq!(Metric::Absolute.distance(&[1, 4, 2]), 5);
+        assert_eq!(Metric::Squared.distance(&[1, 4, 2]), 13);
+        assert_eq!(Metric::MaxSpread.distance(&[1, 4, 2]), 3);
+        assert_eq!(Metric::MaxSpread.distance(&[7]), 0);
+        assert_eq!("max-spread".parse(), Ok(Metric::MaxSpread));
+        assert!("manhattan".parse::<Metric>().is_err());
+    }
+
+    #[test]
+    fn test_n_columns() {
+        let lists = Lists::read("3 4 9\n1 3 2\n2 5 4\n".as_bytes()).unwrap();
+        let pairings = lists.pairings(Metric::MaxSpread);
+        assert_eq!(pairings[0].ids, vec![1, 3, 2]);
+        assert_eq!(pairings[2].ids, vec![3, 5, 9]);
+        assert_eq!(lists.total_distance(Metric::MaxSpread), 2 + 2 + 6);
+        assert_eq!(lists.total_distance(Metric::Squared), 5 + 4 + 20);
+    }
+
+    #[test]
+    fn test_n_columns_errors() {
+        let err = Lists::read("3 4 9\n1 3\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (2, "expected 3 columns"));
+        let err = Lists::read("3\n".as_bytes()).unwrap_err();
+        assert_eq!(err.message, "expected at least 2 columns");
+    }
+
+    #[test]
+    fn test_report() {
+        let lists = Day01::parse(include_str!("../example.txt")).unwrap();
+        let report = lists.report(Metric::Absolute);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[1], "     1  1 3             2   18.2%");
+        assert_eq!(lines[7], " total                 11");
+    }
+}
@@ -1,50 +1,73 @@
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::fs::File;
+use aoc_common::{
+    day::Solution,
+    input::{stream_input, Source},
+};
+use day01::{
+    external::{SpilledLists, DEFAULT_RUN_LEN},
+    Day01, Lists, Metric,
+};
 
+// Usage: day01 [--external] [--metric absolute|squared|max-spread] [--report] [input]
+//
+// `--external` sorts the lists in runs on disk, for inputs larger than memory.
+// `--metric` picks how far apart the paired ids are, `--report` prints every pairing.
+// Without `--external` the input may have any number of columns.
 fn main() {
-    let path = Path::new("input.txt");
-
-    let file = match File::open(&path) {
-        Err(why) => panic!("couldn't open {}: {}", path.display(), why),
-        Ok(file) => file,
-    };
-
-
-    let mut a: Vec<i32> = Vec::new();
-    let mut b: Vec<i32> = Vec::new();
-    io::BufReader::new(file).lines().for_each(|line| {
-        let line = line.unwrap();
-        println!("{}", line);
-
-        let words: Vec<&str> = line.split("   ").collect();
-        let av = words[0].parse::<i32>().unwrap();
-        let bv = words[1].parse::<i32>().unwrap();
-
-        a.push(av);
-        b.push(bv);
-    });
-
-    a.sort();
-    b.sort();
+    let mut external = false;
+    let mut metric = Metric::Absolute;
+    let mut report = false;
+    let mut source = day01::DAY.input.source();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--external" => external = true,
+            "--report" => report = true,
+            "--metric" => match args.next().unwrap_or_default().parse() {
+                Err(why) => {
+                    eprintln!("error: {}", why);
+                    std::process::exit(1);
+                }
+                Ok(m) => metric = m,
+            },
+            _ => source = arg.parse().unwrap(),
+        }
+    }
+    if external && (report || metric != Metric::Absolute) {
+        eprintln!("error: --external only supports the absolute metric without --report");
+        std::process::exit(1);
+    }
+    // Stream the lists in, generated inputs can have millions of rows
+    if external {
+        run_external(&source);
+        return;
+    }
+    let lists = stream_input(&source, Lists::read);
 
-    let mut sum = 0;
-    for i in 0..a.len() {
-        sum += (a[i] - b[i]).abs();
+    if report {
+        print!("{}", lists.report(metric));
+        return;
     }
-    println!("{}", sum);
+
+    println!("{}", lists.total_distance(metric));
 
     // --- Part Two ---
 
-    let mut sum = 0;
-    for i in 0..a.len() {
-        let mut found = 0;
-        for j in 0..b.len() {
-            if a[i] == b[j] {
-                found += 1;
-            }
+    println!("{}", Day01::part2(&lists));
+}
+
+fn run_external(source: &Source) {
+    let lists = stream_input(source, |reader| SpilledLists::read(reader, DEFAULT_RUN_LEN));
+    let answers = lists
+        .total_distance()
+        .and_then(|part1| Ok((part1, lists.similarity_score()?)));
+    match answers {
+        Err(why) => {
+            eprintln!("error: couldn't merge sorted runs: {}", why);
+            std::process::exit(1);
+        }
+        Ok((part1, part2)) => {
+            println!("{}", part1);
+            println!("{}", part2);
         }
-        sum += a[i] * found;
     }
-    println!("{}", sum);
 }
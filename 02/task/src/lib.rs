@@ -0,0 +1,437 @@
+use aoc_common::{
+    day::{Day, Example, Part, Solution},
+    error::ParseError,
+    parse::{lines, read_lines},
+};
+use std::{fmt, io::BufRead};
+
+pub const DAY: Day = Day::new::<Day02>(aoc_common::default_input!());
+
+pub struct Day02;
+
+// Read the reports line by line, e.g. from stdin, each parsed once into its levels
+pub fn read_reports(reader: impl BufRead) -> Result<Vec<Vec<i32>>, ParseError> {
+    let mut reports = Vec::new();
+    read_lines(reader, |line| {
+        reports.push(line.numbers(" ")?);
+        Ok(())
+    })?;
+    Ok(reports)
+}
+
+// Why an adjacent pair of levels makes a report unsafe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    // Increasing where the report was decreasing or the other way round
+    DirectionChange,
+    // Two equal levels
+    NoChange,
+    // The levels differ by more than 3
+    StepTooLarge(i32),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::DirectionChange => write!(f, "direction change"),
+            Violation::NoChange => write!(f, "step of 0"),
+            Violation::StepTooLarge(step) => write!(f, "step of {} above 3", step),
+        }
+    }
+}
+
+// The first adjacent pair breaking the rules, `index` is the position of its first level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Problem {
+    pub index: usize,
+    pub violation: Violation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportVerdict {
+    Safe,
+    // `fix` is the first level whose removal makes the report safe, if any
+    Unsafe {
+        problem: Problem,
+        fix: Option<usize>,
+    },
+}
+
+impl ReportVerdict {
+    pub fn of(levels: &[i32]) -> ReportVerdict {
+        match first_problem(levels) {
+            None => ReportVerdict::Safe,
+            Some(problem) => ReportVerdict::Unsafe {
+                problem,
+                fix: (0..levels.len()).find(|&i| check_numbers(&without(levels, i))),
+            },
+        }
+    }
+
+    pub fn is_safe(&self) -> bool {
+        *self == ReportVerdict::Safe
+    }
+
+    // Safe with the Problem Dampener, i.e. after removing at most one level
+    pub fn is_safe_dampened(&self) -> bool {
+        match self {
+            ReportVerdict::Safe => true,
+            ReportVerdict::Unsafe { fix, .. } => fix.is_some(),
+        }
+    }
+}
+
+// The direction is set by the first pair that changes at all
+fn first_problem(levels: &[i32]) -> Option<Problem> {
+    let direction = levels
+        .windows(2)
+        .map(|w| (w[1] - w[0]).signum())
+        .find(|&d| d != 0)
+        .unwrap_or(1);
+    levels.windows(2).enumerate().find_map(|(index, w)| {
+        let step = w[1] - w[0];
+        let violation = if step == 0 {
+            Violation::NoChange
+        } else if step.signum() != direction {
+            Violation::DirectionChange
+        } else if step.abs() > 3 {
+            Violation::StepTooLarge(step.abs())
+        } else {
+            return None;
+        };
+        Some(Problem { index, violation })
+    })
+}
+
+fn without(levels: &[i32], index: usize) -> Vec<i32> {
+    let mut levels = levels.to_vec();
+    levels.remove(index);
+    levels
+}
+
+fn check_numbers(numbers: &[i32]) -> bool {
+    first_problem(numbers).is_none()
+}
+
+// Decides whether a report is safe after removing up to `tolerance` levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dampener {
+    pub tolerance: usize,
+    // Allowed size of a step between two kept levels
+    pub min_step: i32,
+    pub max_step: i32,
+}
+
+impl Dampener {
+    pub const PART_ONE: Dampener = Dampener::new(0);
+    pub const PART_TWO: Dampener = Dampener::new(1);
+
+    pub const fn new(tolerance: usize) -> Dampener {
+        Dampener {
+            tolerance,
+            min_step: 1,
+            max_step: 3,
+        }
+    }
+
+    pub fn is_safe(&self, levels: &[i32]) -> bool {
+        [1, -1]
+            .into_iter()
+            .any(|direction| self.removals(levels, direction) <= self.tolerance)
+    }
+
+    pub fn count_safe(&self, reports: &[Vec<i32>]) -> usize {
+        reports.iter().filter(|levels| self.is_safe(levels)).count()
+    }
+
+    // Fewest levels to remove so the rest steps in `direction` within the bounds
+    //
+    // A kept level can only follow one of the `tolerance + 1` levels before it, as
+    // skipping more would exceed the tolerance, so a single pass remembering that
+    // window suffices: O(n * k) instead of trying every combination of removals.
+    fn removals(&self, levels: &[i32], direction: i32) -> usize {
+        // A larger tolerance than the report has levels can't skip any further back
+        let window = self.tolerance.min(levels.len()) + 1;
+        // Fewest removals with level `i` as the last one kept, at `i % window`
+        let mut kept_last = vec![usize::MAX; window];
+        // Removing everything leaves a trivially safe report
+        let mut fewest = levels.len();
+        for (i, &level) in levels.iter().enumerate() {
+            // Keeping it as the first level means removing all before it
+            let mut removals = i;
+            for back in 1..=window.min(i) {
+                let previous = kept_last[(i - back) % window];
+                if previous != usize::MAX && self.allows(level - levels[i - back], direction) {
+                    removals = removals.min(previous + back - 1);
+                }
+            }
+            kept_last[i % window] = removals;
+            fewest = fewest.min(removals + levels.len() - 1 - i);
+        }
+        fewest
+    }
+
+    fn allows(&self, step: i32, direction: i32) -> bool {
+        (self.min_step..=self.max_step).contains(&(step * direction))
+    }
+}
+
+// One line per report with the verdict for both parts and the reason
+pub fn explain(reports: &[Vec<i32>]) -> String {
+    let levels: Vec<String> = reports
+        .iter()
+        .map(|report| {
+            report
+                .iter()
+                .map(|level| level.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    let width = levels.iter().map(|l| l.len()).max().unwrap_or(0).max(6);
+
+    let mut out = format!(
+        "{:>5}  {:<width$}  {:<8}  reason\n",
+        "line", "levels", "verdict"
+    );
+    for (i, (report, text)) in reports.iter().zip(&levels).enumerate() {
+        let (verdict, reason) = match ReportVerdict::of(report) {
+            ReportVerdict::Safe => ("safe", String::new()),
+            ReportVerdict::Unsafe { problem, fix } => {
+                let a = report[problem.index];
+                let b = report[problem.index + 1];
+                let reason = format!("{} -> {} is a {}", a, b, problem.violation);
+                match fix {
+                    Some(fix) => (
+                        "dampened",
+                        format!(
+                            "{}, safe without level {} ({})",
+                            reason,
+                            fix + 1,
+                            report[fix]
+                        ),
+                    ),
+                    None => ("unsafe", format!("{}, no single removal helps", reason)),
+                }
+            }
+        };
+        let line = format!("{:>5}  {:<width$}  {:<8}  {}", i + 1, text, verdict, reason);
+        out += line.trim_end();
+        out += "\n";
+    }
+    out
+}
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            part: Part::One,
+            input: include_str!("../example.txt"),
+            answer: "2",
+        },
+        Example {
+            part: Part::Two,
+            input: include_str!("../example.txt"),
+            answer: "4",
+        },
+    ];
+
+    type Input = Vec<Vec<i32>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    // Every line is one report of space separated levels
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+        lines(input).map(|line| line.numbers(" ")).collect()
+    }
+
+    fn part1(reports: &Vec<Vec<i32>>) -> usize {
+        Dampener::PART_ONE.count_safe(reports)
+    }
+
+    fn part2(reports: &Vec<Vec<i32>>) -> usize {
+        Dampener::PART_TWO.count_safe(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::day::check_examples;
+
+    #[test]
+    fn test_examples() {
+        check_examples::<Day02>();
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let verdicts: Vec<ReportVerdict> = Day02::parse(include_str!("../example.txt"))
+            .unwrap()
+            .iter()
+            .map(|report| ReportVerdict::of(report))
+            .collect();
+        let unsafe_at = |index, violation, fix| ReportVerdict::Unsafe {
+            problem: Problem { index, violation },
+            fix,
+        };
+        assert_eq!(
+            verdicts,
+            vec![
+                ReportVerdict::Safe,
+                unsafe_at(1, Violation::StepTooLarge(5), None),
+                unsafe_at(2, Violation::StepTooLarge(4), None),
+                unsafe_at(1, Violation::DirectionChange, Some(1)),
+                unsafe_at(2, Violation::NoChange, Some(2)),
+                ReportVerdict::Safe,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_direction_from_first_change() {
+        assert_eq!(
+            ReportVerdict::of(&[5, 5, 4, 3]),
+            ReportVerdict::Unsafe {
+                problem: Problem {
+                    index: 0,
+                    violation: Violation::NoChange
+                },
+                fix: Some(0),
+            }
+        );
+        assert!(!ReportVerdict::of(&[3, 3]).is_safe());
+        assert!(ReportVerdict::of(&[3, 3]).is_safe_dampened());
+        assert!(ReportVerdict::of(&[1]).is_safe());
+    }
+
+    #[test]
+    fn test_explain() {
+        let reports = Day02::parse(include_str!("../example.txt")).unwrap();
+        let table = explain(&reports);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[1], "    1  7 6 4 2 1  safe");
+        assert_eq!(
+            lines[2],
+            "    2  1 2 7 8 9  unsafe    2 -> 7 is a step of 5 above 3, no single removal helps"
+        );
+        assert_eq!(
+            lines[4],
+            "    4  1 3 2 4 5  dampened  3 -> 2 is a direction change, safe without level 2 (3)"
+        );
+    }
+
+    // Try every way of removing up to `dampener.tolerance` levels
+    fn brute_force(levels: &[i32], dampener: &Dampener) -> bool {
+        let steps_ok = |levels: &[i32], direction: i32| {
+            levels.windows(2).all(|w| {
+                (dampener.min_step..=dampener.max_step).contains(&((w[1] - w[0]) * direction))
+            })
+        };
+        if steps_ok(levels, 1) || steps_ok(levels, -1) {
+            return true;
+        }
+        if dampener.tolerance == 0 {
+            return false;
+        }
+        let fewer = Dampener {
+            tolerance: dampener.tolerance - 1,
+            ..*dampener
+        };
+        (0..levels.len()).any(|i| brute_force(&without(levels, i), &fewer))
+    }
+
+    #[test]
+    fn test_dampener_matches_brute_force() {
+        // Small deterministic pseudo random reports, so all kinds of steps show up
+        let mut seed: u64 = 0x2024;
+        let mut next = |bound: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % bound
+        };
+        let dampeners = [
+            Dampener::new(0),
+            Dampener::new(1),
+            Dampener::new(2),
+            Dampener::new(3),
+            Dampener {
+                tolerance: 1,
+                min_step: 0,
+                max_step: 2,
+            },
+            Dampener {
+                tolerance: 2,
+                min_step: 2,
+                max_step: 5,
+            },
+        ];
+        for _ in 0..2000 {
+            let len = next(9) as usize;
+            let mut levels = vec![next(20) as i32];
+            for _ in 1..len {
+                let step = next(11) as i32 - 5;
+                levels.push(levels.last().unwrap() + step);
+            }
+            for dampener in &dampeners {
+                assert_eq!(
+                    dampener.is_safe(&levels),
+                    brute_force(&levels, dampener),
+                    "{:?} with {:?}",
+                    levels,
+                    dampener
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_dampener_matches_verdict() {
+        let reports = Day02::parse(include_str!("../example.txt")).unwrap();
+        for report in &reports {
+            let verdict = ReportVerdict::of(report);
+            assert_eq!(Dampener::PART_ONE.is_safe(report), verdict.is_safe());
+            assert_eq!(
+                Dampener::PART_TWO.is_safe(report),
+                verdict.is_safe_dampened()
+            );
+        }
+    }
+
+    #[test]
+    fn test_dampener_tolerance() {
+        assert!(!Dampener::new(1).is_safe(&[1, 9, 2, 9, 3]));
+        assert!(Dampener::new(2).is_safe(&[1, 9, 2, 9, 3]));
+        assert!(Dampener::new(2).is_safe(&[7, 7]));
+        assert!(Dampener::new(0).is_safe(&[]));
+        assert!(Dampener::new(usize::MAX).is_safe(&[1, 2, 3]));
+        assert!(Dampener::new(100_000_000_000).is_safe(&[1, 9, 2, 9, 3]));
+    }
+
+    // Only implements `Read`, like a pipe that can't seek back to the start
+    struct Pipe<'a>(&'a [u8]);
+
+    impl std::io::Read for Pipe<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            // Hand out a few bytes at a time so lines arrive split across reads
+            let n = buf.len().min(self.0.len()).min(3);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_read_reports_from_pipe() {
+        let input = include_str!("../example.txt");
+        let reports = read_reports(std::io::BufReader::new(Pipe(input.as_bytes()))).unwrap();
+        assert_eq!(reports, Day02::parse(input).unwrap());
+        assert_eq!(Day02::part1(&reports), 2);
+        assert_eq!(Day02::part2(&reports), 4);
+
+        let err = read_reports(std::io::BufReader::new(Pipe(b"1 2\n3 x 4\n"))).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "x"));
+    }
+}
@@ -1,84 +1,43 @@
-use core::fmt;
-use std::io::{self, BufRead, Seek, SeekFrom};
-use std::path::Path;
-use std::fs::File;
+use aoc_common::{day::Solution, input::stream_input};
+use day02::{read_reports, Dampener, Day02};
 
-fn checkNumbers(numbers: &Vec<i32>) -> bool {
-    // Combine adjacent numbers with zip to iterate over pairs
-    let pairs: Vec<(&i32, &i32)> = numbers.iter().zip(numbers.iter().skip(1)).collect();
-    
-    // Reduce the pairs to the difference between the two numbers
-    let differences: Vec<i32> = pairs.iter().map(|(a, b)| *b - *a).collect();
-
-    // Check if all differences have the same sign
-    let sameSign = differences.iter().all(|&x| x > 0) || differences.iter().all(|&x| x < 0);
-
-    // Conver to absolute values
-    let absDifferences: Vec<i32> = differences.iter().map(|&x| x.abs()).collect();
-
-    // Check if all value are between 1 and 3
-    let inRange = absDifferences.iter().all(|&x| x >= 1 && x <= 3);
-
-    return sameSign && inRange;
-}
-
-fn checkWithUpToOneRemoved(numbers: Vec<i32>) -> bool {
-    if checkNumbers(&numbers) {
-        return true;
-    }
-    let mut numbers = numbers.clone();
-    for i in 0..numbers.len() {
-        let mut numbers = numbers.clone();
-        numbers.remove(i);
-        if checkNumbers(&numbers) {
-            return true;
+// Usage: day02 [--explain] [--tolerance K] [input]
+//
+// `--explain` prints a table with the verdict and reason for every report.
+// `--tolerance` counts the reports that are safe with up to K levels removed.
+fn main() {
+    let mut explain = false;
+    let mut tolerance = None;
+    let mut source = day02::DAY.input.source();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--explain" => explain = true,
+            "--tolerance" => match args.next().and_then(|k| k.parse().ok()) {
+                None => {
+                    eprintln!("error: --tolerance expects a number of levels");
+                    std::process::exit(1);
+                }
+                Some(k) => tolerance = Some(k),
+            },
+            _ => source = arg.parse().unwrap(),
         }
     }
-    return false;
-}
-
-fn main() {
-    let path = Path::new("input.txt");
-
-    let mut file = match File::open(&path) {
-        Err(why) => panic!("couldn't open {}: {}", path.display(), why),
-        Ok(file) => file,
-    };
+    // Parse once while streaming, so pipes work as well as files
+    let reports = stream_input(&source, read_reports);
 
-    let mut saveCount = 0;
-    io::BufReader::new(file.try_clone().unwrap()).lines().for_each(|line| {
-        let line = line.unwrap();
-        println!("{}", line);
-
-        let words: Vec<&str> = line.split(" ").collect();
+    if explain {
+        print!("{}", day02::explain(&reports));
+        return;
+    }
+    if let Some(k) = tolerance {
+        println!("{}", Dampener::new(k).count_safe(&reports));
+        return;
+    }
 
-        let numbers: Vec<i32> = words.iter().map(|x| x.parse::<i32>().unwrap()).collect();
-        if checkNumbers(&numbers) {
-            saveCount += 1;
-        }
-    });
     std::println!("--- PART 1 ---");
-    std::println!("{}", saveCount);
-
-    // Skeek back to the beginning of the file
-    file.seek(SeekFrom::Start(0)).unwrap();
-
-    let mut saveCount = 0;
-    io::BufReader::new(file).lines().for_each(|line| {
-        let line = line.unwrap();
-        println!("{}", line);
-
-        let words: Vec<&str> = line.split(" ").collect();
-
-        // First parse every word in the line to i32
-        let numbers: Vec<i32> = words.iter().map(|x| x.parse::<i32>().unwrap()).collect();
-
-        if checkWithUpToOneRemoved(numbers) {
-            saveCount += 1;
-        }
-    });
-
+    std::println!("{}", Day02::part1(&reports));
 
     std::println!("--- PART 2 ---");
-    std::println!("{}", saveCount);
+    std::println!("{}", Day02::part2(&reports));
 }
@@ -0,0 +1,173 @@
+use crate::{
+    instruction::InstructionSet,
+    interpreter::Interpreter,
+    lexer::{Token, TokenKind},
+};
+use std::io::{self, BufRead};
+
+// Bytes read from the input at a time
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+// Splits corrupted memory into instructions and the junk around them in one pass,
+// reading it from a stream in fixed-size chunks
+//
+// Runs of junk are merged into one token, so instructions and junk alternate.
+// Only the unscanned rest of the current chunk plus enough lookahead for the
+// longest instruction is kept, so instructions split across chunks are still
+// found while memory stays constant however large the input is.
+pub struct ChunkedLexer<'a, R> {
+    reader: R,
+    instructions: &'a InstructionSet,
+    chunk_size: usize,
+    lookahead: usize,
+    // Bytes read but not scanned yet start at `position`, `buffer[0]` is at `offset` in the input
+    buffer: Vec<u8>,
+    position: usize,
+    offset: usize,
+    eof: bool,
+}
+
+impl<'a, R: BufRead> ChunkedLexer<'a, R> {
+    pub fn new(reader: R, instructions: &'a InstructionSet, chunk_size: usize) -> Self {
+        ChunkedLexer {
+            reader,
+            instructions,
+            chunk_size: chunk_size.max(1),
+            lookahead: instructions.max_len(),
+            buffer: Vec::new(),
+            position: 0,
+            offset: 0,
+            eof: false,
+        }
+    }
+
+    // Read chunks until a whole instruction at `position` would be in view
+    fn fill(&mut self) -> io::Result<()> {
+        while !self.eof && self.buffer.len() - self.position < self.lookahead {
+            self.buffer.drain(..self.position);
+            self.offset += self.position;
+            self.position = 0;
+
+            let len = self.buffer.len();
+            self.buffer.resize(len + self.chunk_size, 0);
+            match self.reader.read(&mut self.buffer[len..]) {
+                Ok(read) => {
+                    self.buffer.truncate(len + read);
+                    self.eof = read == 0;
+                }
+                Err(why) => {
+                    self.buffer.truncate(len);
+                    if why.kind() != io::ErrorKind::Interrupted {
+                        return Err(why);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn decode(&self) -> Option<(usize, Vec<i64>, usize)> {
+        self.instructions.decode(&self.buffer[self.position..])
+    }
+
+    fn next_token(&mut self) -> io::Result<Option<Token>> {
+        self.fill()?;
+        if self.position >= self.buffer.len() {
+            return Ok(None);
+        }
+        let start = self.offset + self.position;
+        if let Some((opcode, operands, len)) = self.decode() {
+            self.position += len;
+            return Ok(Some(Token {
+                kind: TokenKind::Instruction { opcode, operands },
+                span: start..start + len,
+            }));
+        }
+        // A failed instruction only skips one byte, another one may start inside it
+        self.position += 1;
+        loop {
+            self.fill()?;
+            if self.position >= self.buffer.len() || self.decode().is_some() {
+                break;
+            }
+            self.position += 1;
+        }
+        Ok(Some(Token {
+            kind: TokenKind::Junk,
+            span: start..self.offset + self.position,
+        }))
+    }
+}
+
+impl<R: BufRead> Iterator for ChunkedLexer<'_, R> {
+    type Item = io::Result<Token>;
+
+    fn next(&mut self) -> Option<io::Result<Token>> {
+        self.next_token().transpose()
+    }
+}
+
+// Both part sums in one pass over the stream
+pub fn scan(
+    reader: impl BufRead,
+    instructions: &InstructionSet,
+    chunk_size: usize,
+) -> io::Result<(i64, i64)> {
+    let mut part1 = Interpreter::new(instructions, false);
+    let mut part2 = Interpreter::new(instructions, true);
+    for token in ChunkedLexer::new(reader, instructions, chunk_size) {
+        let token = token?;
+        for interpreter in [&mut part1, &mut part2] {
+            interpreter
+                .step(&token)
+                .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))?;
+        }
+    }
+    Ok((part1.state().sum, part2.state().sum))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{instruction::Instruction, lexer::Lexer};
+
+    // Every chunk size has to give the same tokens as the whole input in one chunk
+    fn check_chunk_sizes(memory: &str, set: &InstructionSet) {
+        let expected: Vec<Token> = Lexer::new(memory, set).collect();
+        for chunk_size in 1..=20 {
+            let tokens: Vec<Token> = ChunkedLexer::new(memory.as_bytes(), set, chunk_size)
+                .collect::<io::Result<_>>()
+                .unwrap();
+            assert_eq!(tokens, expected, "chunk size {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn test_chunk_sizes() {
+        let set = InstructionSet::puzzle();
+        check_chunk_sizes(include_str!("../example.txt"), &set);
+        check_chunk_sizes(include_str!("../example2.txt"), &set);
+        check_chunk_sizes("mul(mul(mul(123,456)don't(do()", &set);
+        check_chunk_sizes("", &set);
+    }
+
+    #[test]
+    fn test_long_registered_instruction() {
+        let mut set = InstructionSet::puzzle();
+        set.register(Instruction {
+            digits: 1..=12,
+            signed: true,
+            ..Instruction::new("add", 2, |state, operands| {
+                state.add(operands[0].checked_add(operands[1]))
+            })
+        });
+        check_chunk_sizes("xxadd(-123456789012,999999999999)mul(2,2)add(1,2", &set);
+    }
+
+    #[test]
+    fn test_scan() {
+        let set = InstructionSet::puzzle();
+        let memory = include_str!("../example2.txt");
+        assert_eq!(scan(memory.as_bytes(), &set, 5).unwrap(), (161, 48));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::interpreter::State;
+use std::ops::RangeInclusive;
+
+// Longest operand that always fits in an i64
+const MAX_DIGITS: usize = 18;
+
+// What an instruction does with its operands, `None` if a result overflowed
+pub type Semantics = fn(&mut State, &[i64]) -> Option<()>;
+
+// One entry of the instruction table, written as `name(a,b,...)` in memory
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub arity: usize,
+    // How many digits every operand may have
+    pub digits: RangeInclusive<usize>,
+    // Whether operands may start with a `-`
+    pub signed: bool,
+    pub semantics: Semantics,
+}
+
+impl Instruction {
+    // An instruction taking unsigned 1 to 3 digit operands, like the puzzle's
+    pub fn new(name: &'static str, arity: usize, semantics: Semantics) -> Instruction {
+        Instruction {
+            name,
+            arity,
+            digits: 1..=3,
+            signed: false,
+            semantics,
+        }
+    }
+
+    // Longest text a call can take up in memory
+    pub fn max_len(&self) -> usize {
+        let operand = self.signed as usize + (*self.digits.end()).min(MAX_DIGITS);
+        self.name.len() + 2 + self.arity * operand + self.arity.saturating_sub(1)
+    }
+
+    // The call as it appears in memory, e.g. `mul(2,4)`
+    pub fn format(&self, operands: &[i64]) -> String {
+        let operands: Vec<String> = operands.iter().map(|x| x.to_string()).collect();
+        format!("{}({})", self.name, operands.join(","))
+    }
+}
+
+// The instructions the lexer recognises, indexed by opcode
+#[derive(Debug, Clone, Default)]
+pub struct InstructionSet {
+    instructions: Vec<Instruction>,
+}
+
+impl InstructionSet {
+    // `mul(a,b)`, `do()` and `don't()`
+    pub fn puzzle() -> InstructionSet {
+        let mut set = InstructionSet::default();
+        set.register(Instruction::new("mul", 2, |state, operands| {
+            state.add(operands[0].checked_mul(operands[1]))
+        }));
+        set.register(Instruction::new("do", 0, |state, _| {
+            state.enabled = true;
+            Some(())
+        }));
+        set.register(Instruction::new("don't", 0, |state, _| {
+            state.enabled = false;
+            Some(())
+        }));
+        set
+    }
+
+    // Add an instruction, returning its opcode
+    pub fn register(&mut self, instruction: Instruction) -> usize {
+        self.instructions.push(instruction);
+        self.instructions.len() - 1
+    }
+
+    pub fn get(&self, opcode: usize) -> &Instruction {
+        &self.instructions[opcode]
+    }
+
+    // Bytes the lexer has to see ahead to be sure no instruction is cut off
+    pub fn max_len(&self) -> usize {
+        self.instructions
+            .iter()
+            .map(|i| i.max_len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    // The instruction written at the start of `memory` with its opcode, operands and length
+    pub fn decode(&self, memory: &[u8]) -> Option<(usize, Vec<i64>, usize)> {
+        self.instructions
+            .iter()
+            .enumerate()
+            .find_map(|(opcode, instruction)| {
+                let (operands, len) = decode_call(instruction, memory)?;
+                Some((opcode, operands, len))
+            })
+    }
+}
+
+fn decode_call(instruction: &Instruction, memory: &[u8]) -> Option<(Vec<i64>, usize)> {
+    let mut cursor = Cursor {
+        rest: memory,
+        read: 0,
+    };
+    cursor.expect(instruction.name.as_bytes())?;
+    cursor.expect(b"(")?;
+    let mut operands = Vec::with_capacity(instruction.arity);
+    for i in 0..instruction.arity {
+        if i > 0 {
+            cursor.expect(b",")?;
+        }
+        operands.push(cursor.operand(instruction)?);
+    }
+    cursor.expect(b")")?;
+    Some((operands, cursor.read))
+}
+
+// Reads the parts of one instruction
+struct Cursor<'a> {
+    rest: &'a [u8],
+    read: usize,
+}
+
+impl Cursor<'_> {
+    fn expect(&mut self, text: &[u8]) -> Option<()> {
+        self.rest = self.rest.strip_prefix(text)?;
+        self.read += text.len();
+        Some(())
+    }
+
+    fn operand(&mut self, instruction: &Instruction) -> Option<i64> {
+        let negative = instruction.signed && self.expect(b"-").is_some();
+        let digits = self.rest.iter().take_while(|c| c.is_ascii_digit()).count();
+        if !instruction.digits.contains(&digits) || digits > MAX_DIGITS {
+            return None;
+        }
+        let value = self.rest[..digits]
+            .iter()
+            .fold(0, |value, c| value * 10 + (c - b'0') as i64);
+        self.rest = &self.rest[digits..];
+        self.read += digits;
+        Some(if negative { -value } else { value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let set = InstructionSet::puzzle();
+        assert_eq!(set.decode(b"mul(12,345)x"), Some((0, vec![12, 345], 11)));
+        assert_eq!(set.decode(b"do()"), Some((1, vec![], 4)));
+        assert_eq!(set.decode(b"don't()"), Some((2, vec![], 7)));
+        assert_eq!(set.decode(b"mul(1234,5)"), None);
+        assert_eq!(set.decode(b"mul(1,2,3)"), None);
+        assert_eq!(set.decode(b"mul(-1,2)"), None);
+        assert_eq!(set.decode(b"do(1)"), None);
+        assert_eq!(set.get(0).format(&[12, 345]), "mul(12,345)");
+        assert_eq!(set.get(2).format(&[]), "don't()");
+        assert_eq!(set.max_len(), "mul(123,456)".len());
+    }
+
+    #[test]
+    fn test_register() {
+        let mut set = InstructionSet::puzzle();
+        let neg = set.register(Instruction {
+            digits: 1..=6,
+            signed: true,
+            ..Instruction::new("neg", 1, |state, operands| {
+                state.add(operands[0].checked_neg())
+            })
+        });
+        assert_eq!(set.get(neg).name, "neg");
+        assert_eq!(set.decode(b"neg(-123456)"), Some((neg, vec![-123456], 12)));
+        assert_eq!(set.decode(b"neg(1234567)"), None);
+        assert_eq!(set.get(neg).max_len(), "neg(-123456)".len());
+        assert_eq!(set.max_len(), "neg(-123456)".len());
+    }
+}
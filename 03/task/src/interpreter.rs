@@ -0,0 +1,150 @@
+use crate::{
+    instruction::InstructionSet,
+    lexer::{Token, TokenKind},
+};
+use std::{error::Error, fmt};
+
+// What instructions can read and change while running
+#[derive(Debug, Clone)]
+pub struct State {
+    // Whether `do()` and `don't()` switch `mul` on and off, only in part 2
+    pub conditionals: bool,
+    pub enabled: bool,
+    pub sum: i64,
+}
+
+impl State {
+    // Add to the sum unless disabled, `None` if the value or the new sum doesn't fit
+    //
+    // Instructions pass their result as computed with `checked_*` arithmetic, so an
+    // overflow only counts when the result would actually be added.
+    pub fn add(&mut self, value: Option<i64>) -> Option<()> {
+        if self.enabled || !self.conditionals {
+            self.sum = self.sum.checked_add(value?)?;
+        }
+        Some(())
+    }
+}
+
+// An instruction whose result, or the sum after adding it, doesn't fit in an i64
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    // Byte offset of the instruction in memory
+    pub offset: usize,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "sum overflows an i64 at byte {}", self.offset)
+    }
+}
+
+impl Error for Overflow {}
+
+// Executes a token stream against the semantics in the instruction table
+pub struct Interpreter<'a> {
+    instructions: &'a InstructionSet,
+    state: State,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(instructions: &'a InstructionSet, conditionals: bool) -> Interpreter<'a> {
+        Interpreter {
+            instructions,
+            state: State {
+                conditionals,
+                enabled: true,
+                sum: 0,
+            },
+        }
+    }
+
+    pub fn step(&mut self, token: &Token) -> Result<(), Overflow> {
+        if let TokenKind::Instruction { opcode, operands } = &token.kind {
+            let semantics = self.instructions.get(*opcode).semantics;
+            semantics(&mut self.state, operands).ok_or(Overflow {
+                offset: token.span.start,
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+}
+
+pub fn run<'a>(
+    instructions: &InstructionSet,
+    tokens: impl IntoIterator<Item = &'a Token>,
+    conditionals: bool,
+) -> Result<i64, Overflow> {
+    let mut interpreter = Interpreter::new(instructions, conditionals);
+    for token in tokens {
+        interpreter.step(token)?;
+    }
+    Ok(interpreter.state().sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{instruction::Instruction, lexer::Lexer};
+
+    #[test]
+    fn test_conditionals() {
+        let set = InstructionSet::puzzle();
+        let tokens: Vec<Token> =
+            Lexer::new("mul(2,3)don't()mul(4,5)do()mul(1,7)don't()", &set).collect();
+        assert_eq!(run(&set, &tokens, false), Ok(6 + 20 + 7));
+        assert_eq!(run(&set, &tokens, true), Ok(6 + 7));
+    }
+
+    #[test]
+    fn test_registered_instructions() {
+        let mut set = InstructionSet::puzzle();
+        set.register(Instruction::new("add", 2, |state, operands| {
+            state.add(operands[0].checked_add(operands[1]))
+        }));
+        set.register(Instruction {
+            digits: 1..=5,
+            ..Instruction::new("neg", 1, |state, operands| {
+                state.add(operands[0].checked_neg())
+            })
+        });
+        let memory = "add(1,2)xmul(3,4)neg(10000)don't()add(5,5)do()neg(2)";
+        let tokens: Vec<Token> = Lexer::new(memory, &set).collect();
+        assert_eq!(run(&set, &tokens, false), Ok(3 + 12 - 10000 + 10 - 2));
+        assert_eq!(run(&set, &tokens, true), Ok(3 + 12 - 10000 - 2));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut set = InstructionSet::puzzle();
+        set.register(Instruction {
+            digits: 1..=18,
+            signed: true,
+            ..Instruction::new("wide", 2, |state, operands| {
+                state.add(operands[0].checked_mul(operands[1]))
+            })
+        });
+        let run_memory = |memory: &str, conditionals| {
+            let tokens: Vec<Token> = Lexer::new(memory, &set).collect();
+            run(&set, &tokens, conditionals)
+        };
+        let max = "999999999999999999";
+        let memory = format!("mul(1,2)wide({},-{})", max, max);
+        assert_eq!(run_memory(&memory, false), Err(Overflow { offset: 8 }));
+        // A product that is never added can't overflow the sum
+        let memory = format!("don't()wide({},{})", max, max);
+        assert_eq!(run_memory(&memory, true), Ok(0));
+
+        // Every product fits, but not their sum
+        let memory = format!("wide({},9)xwide({},9)", max, max);
+        assert_eq!(run_memory(&memory, false), Err(Overflow { offset: 27 }));
+        assert_eq!(
+            Overflow { offset: 27 }.to_string(),
+            "sum overflows an i64 at byte 27"
+        );
+    }
+}
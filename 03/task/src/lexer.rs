@@ -0,0 +1,106 @@
+use crate::{chunked::ChunkedLexer, instruction::InstructionSet};
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    // A call of the instruction with this opcode in the instruction set
+    Instruction { opcode: usize, operands: Vec<i64> },
+    // Corrupted memory between instructions
+    Junk,
+}
+
+// A token together with the byte range it was read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Range<usize>,
+}
+
+// Splits corrupted memory held as a string into instructions and the junk around them
+//
+// A `ChunkedLexer` over the whole input as one chunk, which can't fail to read.
+pub struct Lexer<'a> {
+    chunks: ChunkedLexer<'a, &'a [u8]>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str, instructions: &'a InstructionSet) -> Lexer<'a> {
+        Lexer {
+            chunks: ChunkedLexer::new(input.as_bytes(), instructions, input.len()),
+        }
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.chunks.next()?;
+        Some(token.expect("reading from a slice can't fail"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Names and operands of the instructions, junk as `?`
+    fn calls(input: &str) -> Vec<String> {
+        let set = InstructionSet::puzzle();
+        Lexer::new(input, &set)
+            .map(|token| match token.kind {
+                TokenKind::Instruction { opcode, operands } => {
+                    format!("{}{:?}", set.get(opcode).name, operands)
+                }
+                TokenKind::Junk => "?".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        assert_eq!(
+            calls(input),
+            vec![
+                "?",
+                "mul[2, 4]",
+                "?",
+                "don't[]",
+                "?",
+                "mul[5, 5]",
+                "?",
+                "mul[11, 8]",
+                "?",
+                "do[]",
+                "?",
+                "mul[8, 5]",
+                "?"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spans_cover_input() {
+        let set = InstructionSet::puzzle();
+        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let tokens: Vec<Token> = Lexer::new(input, &set).collect();
+        assert_eq!(tokens[0].span, 0..1);
+        assert_eq!(&input[tokens[1].span.clone()], "mul(2,4)");
+        let mut end = 0;
+        for token in &tokens {
+            assert_eq!(token.span.start, end);
+            end = token.span.end;
+        }
+        assert_eq!(end, input.len());
+    }
+
+    #[test]
+    fn test_overlapping_and_invalid() {
+        assert_eq!(calls("mul(mul(2,3)"), vec!["?", "mul[2, 3]"]);
+        assert_eq!(calls("mul(1234,5)mul(,5)"), vec!["?"]);
+        assert_eq!(calls("mul ( 2,3)mul(2, 3)"), vec!["?"]);
+        assert_eq!(calls("don't()do()"), vec!["don't[]", "do[]"]);
+        assert!(calls("").is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+use aoc_common::{
+    day::{Day, Example, Part, Solution},
+    error::ParseError,
+};
+use instruction::InstructionSet;
+use lexer::{Lexer, Token};
+
+pub mod chunked;
+pub mod instruction;
+pub mod interpreter;
+pub mod lexer;
+pub mod listing;
+
+pub const DAY: Day = Day::new::<Day03>(aoc_common::default_input!());
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            part: Part::One,
+            input: include_str!("../example.txt"),
+            answer: "161",
+        },
+        Example {
+            part: Part::Two,
+            input: include_str!("../example2.txt"),
+            answer: "48",
+        },
+    ];
+
+    // The corrupted memory as a stream of instructions and junk
+    type Input = Vec<Token>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Token>, ParseError> {
+        Ok(Lexer::new(input, &InstructionSet::puzzle()).collect())
+    }
+
+    // Puzzle products are below 10^6, so the sum only overflows after far more
+    // instructions than fit in memory
+    fn part1(tokens: &Vec<Token>) -> i64 {
+        interpreter::run(&InstructionSet::puzzle(), tokens, false).unwrap()
+    }
+
+    fn part2(tokens: &Vec<Token>) -> i64 {
+        interpreter::run(&InstructionSet::puzzle(), tokens, true).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::day::check_examples;
+
+    #[test]
+    fn test_examples() {
+        check_examples::<Day03>();
+    }
+}
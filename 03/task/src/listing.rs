@@ -0,0 +1,119 @@
+use crate::{
+    instruction::InstructionSet,
+    interpreter::{Interpreter, Overflow},
+    lexer::{Token, TokenKind},
+};
+
+// One executed instruction, as listed by `--trace`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    // Byte offset of the instruction in memory
+    pub offset: usize,
+    pub text: String,
+    // Whether `mul` was enabled when the instruction ran
+    pub enabled: bool,
+    // The sum after running it
+    pub sum: i64,
+}
+
+// Run the tokens, recording every instruction and the state it ran in
+pub fn trace(
+    instructions: &InstructionSet,
+    tokens: &[Token],
+    conditionals: bool,
+) -> Result<Vec<TraceEntry>, Overflow> {
+    let mut interpreter = Interpreter::new(instructions, conditionals);
+    let mut entries = Vec::new();
+    for token in tokens {
+        let TokenKind::Instruction { opcode, operands } = &token.kind else {
+            continue;
+        };
+        let enabled = interpreter.state().enabled;
+        interpreter.step(token)?;
+        entries.push(TraceEntry {
+            offset: token.span.start,
+            text: instructions.get(*opcode).format(operands),
+            enabled,
+            sum: interpreter.state().sum,
+        });
+    }
+    Ok(entries)
+}
+
+pub fn format_trace(entries: &[TraceEntry]) -> String {
+    let width = entries
+        .iter()
+        .map(|e| e.text.len())
+        .max()
+        .unwrap_or(0)
+        .max(11);
+    let mut out = format!(
+        "{:>10}  {:<width$}  {:<7}  {:>12}\n",
+        "offset", "instruction", "enabled", "sum"
+    );
+    for entry in entries {
+        let enabled = if entry.enabled { "yes" } else { "no" };
+        out += &format!(
+            "{:>10}  {:<width$}  {:<7}  {:>12}\n",
+            entry.offset, entry.text, enabled, entry.sum
+        );
+    }
+    out
+}
+
+// The valid instructions only, one per line, with all corruption stripped
+pub fn disassemble(instructions: &InstructionSet, tokens: &[Token]) -> String {
+    let mut out = String::new();
+    for token in tokens {
+        if let TokenKind::Instruction { opcode, operands } = &token.kind {
+            out += &instructions.get(*opcode).format(operands);
+            out += "\n";
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interpreter, lexer::Lexer};
+
+    const MEMORY: &str = include_str!("../example2.txt");
+
+    #[test]
+    fn test_trace() {
+        let set = InstructionSet::puzzle();
+        let tokens: Vec<Token> = Lexer::new(MEMORY, &set).collect();
+        let entries = trace(&set, &tokens, true).unwrap();
+        assert_eq!(entries.len(), 6);
+        assert_eq!(
+            entries[2],
+            TraceEntry {
+                offset: 28,
+                text: "mul(5,5)".to_string(),
+                enabled: false,
+                sum: 8,
+            }
+        );
+        assert_eq!(entries.last().unwrap().sum, 48);
+        let table = format_trace(&entries);
+        assert_eq!(
+            table.lines().nth(1),
+            Some("         1  mul(2,4)     yes                 8")
+        );
+    }
+
+    #[test]
+    fn test_disassemble_round_trip() {
+        let set = InstructionSet::puzzle();
+        let tokens: Vec<Token> = Lexer::new(MEMORY, &set).collect();
+        let listing = disassemble(&set, &tokens);
+        assert_eq!(
+            listing,
+            "mul(2,4)\ndon't()\nmul(5,5)\nmul(11,8)\ndo()\nmul(8,5)\n"
+        );
+        // The listing is itself valid memory with the same result
+        let stripped: Vec<Token> = Lexer::new(&listing, &set).collect();
+        assert_eq!(interpreter::run(&set, &stripped, true), Ok(48));
+    }
+}
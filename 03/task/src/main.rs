@@ -1,52 +1,65 @@
-use std::{fs::File, io::Read, path::Path};
-
-use regex::Regex;
+use aoc_common::input::{parse_input, Source};
+use day03::{
+    chunked::{self, DEFAULT_CHUNK_SIZE},
+    instruction::InstructionSet,
+    listing, Day03,
+};
 
+// Usage: day03 [--trace | --disassemble] [input]
+//
+// `--trace` lists every instruction with its byte offset, whether `mul` was enabled
+// and the running part 2 sum, `--disassemble` prints the instructions without the
+// corruption around them.
 fn main() {
-
-    let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-
-    let path = Path::new("input.txt");
-
-    let mut file = match File::open(&path) {
-        Err(why) => panic!("couldn't open {}: {}", path.display(), why),
-        Ok(file) => file,
-    };
-
-    let mut contents = String::new();
-    match file.read_to_string(&mut contents) {
-        Err(why) => panic!("couldn't read {}: {}", path.display(), why),
-        Ok(_) => (),
+    let mut trace = false;
+    let mut disassemble = false;
+    let mut source = day03::DAY.input.source();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--trace" => trace = true,
+            "--disassemble" => disassemble = true,
+            _ => source = arg.parse().unwrap(),
+        }
     }
-
-    let mut sum = 0;
-    for cap in re.captures_iter(&contents) {
-        let a: i32 = cap.get(1).unwrap().as_str().parse().unwrap();
-        let b: i32 = cap.get(2).unwrap().as_str().parse().unwrap();
-        sum += a * b;
+    if trace || disassemble {
+        list(&source, trace);
+        return;
     }
-    println!("Part 1");
-    println!("Sum: {}", sum);
 
-    // --- PART 2 ---
+    // Scan in chunks, corrupted dumps can be far larger than memory
+    let sums = source.open().and_then(|reader| {
+        chunked::scan(reader, &InstructionSet::puzzle(), DEFAULT_CHUNK_SIZE)
+            .map_err(|why| format!("{}: {}", source, why))
+    });
+    let (part1, part2) = match sums {
+        Err(why) => {
+            eprintln!("error: {}", why);
+            std::process::exit(1);
+        }
+        Ok(sums) => sums,
+    };
 
-    let mut sum = 0;
-    for cap in re.captures_iter(&contents) {
+    println!("Part 1");
+    println!("Sum: {}", part1);
 
-        let start = cap.get(0).unwrap().start();
-        // check where this first do() is before start()
-        let do_pos = contents[..start].rfind("do()").unwrap_or(0);
-        let dont_pos = contents[..start].rfind("don't()").unwrap_or(0);
+    println!("Part 2");
+    println!("Sum: {}", part2);
+}
 
-        // is enabled when do() is more recent than dont()
-        if do_pos >= dont_pos {
-            let a: i32 = cap.get(1).unwrap().as_str().parse().unwrap();
-            let b: i32 = cap.get(2).unwrap().as_str().parse().unwrap();
-            sum += a * b;
+fn list(source: &Source, trace: bool) {
+    let tokens = parse_input::<Day03>(source);
+    if trace {
+        match listing::trace(&InstructionSet::puzzle(), &tokens, true) {
+            Err(why) => {
+                eprintln!("error: {}", why);
+                std::process::exit(1);
+            }
+            Ok(entries) => print!("{}", listing::format_trace(&entries)),
         }
+    } else {
+        print!(
+            "{}",
+            listing::disassemble(&InstructionSet::puzzle(), &tokens)
+        );
     }
-
-    println!("Part 2");
-    println!("Sum: {}", sum);
-
 }
@@ -0,0 +1,121 @@
+use std::collections::{HashMap, VecDeque};
+
+// Aho-Corasick automaton finding all of a list of words in one pass over a text
+#[derive(Debug, Clone)]
+pub struct Automaton {
+    nodes: Vec<Node>,
+    lens: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    next: HashMap<char, usize>,
+    // Longest proper suffix of this node's prefix that is also a prefix in the trie
+    fail: usize,
+    // Words ending here, including those ending at the nodes down the fail links
+    words: Vec<usize>,
+}
+
+impl Automaton {
+    // Empty words are never matched
+    pub fn new(words: &[&str]) -> Automaton {
+        let mut nodes = vec![Node::default()];
+        let mut lens = Vec::with_capacity(words.len());
+        for (index, word) in words.iter().enumerate() {
+            lens.push(word.chars().count());
+            if word.is_empty() {
+                continue;
+            }
+            let mut node = 0;
+            for c in word.chars() {
+                node = match nodes[node].next.get(&c) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[node].next.insert(c, next);
+                        next
+                    }
+                };
+            }
+            nodes[node].words.push(index);
+        }
+
+        // Breadth first, so the fail target of a node is always done before it
+        let mut queue: VecDeque<usize> = nodes[0].next.values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            let children: Vec<(char, usize)> =
+                nodes[node].next.iter().map(|(&c, &n)| (c, n)).collect();
+            for (c, child) in children {
+                let mut fail = nodes[node].fail;
+                let target = loop {
+                    if let Some(&next) = nodes[fail].next.get(&c) {
+                        break next;
+                    }
+                    if fail == 0 {
+                        break 0;
+                    }
+                    fail = nodes[fail].fail;
+                };
+                nodes[child].fail = target;
+                let inherited = nodes[target].words.clone();
+                nodes[child].words.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+        Automaton { nodes, lens }
+    }
+
+    // Length in chars of word `index`
+    pub fn len(&self, index: usize) -> usize {
+        self.lens[index]
+    }
+
+    fn step(&self, mut node: usize, c: char) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[node].next.get(&c) {
+                return next;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    // Every `(position, word)` where a word ends at the char with that 0-based position
+    pub fn find_all(&self, text: impl IntoIterator<Item = char>) -> Vec<(usize, usize)> {
+        let mut found = Vec::new();
+        let mut node = 0;
+        for (position, c) in text.into_iter().enumerate() {
+            node = self.step(node, c);
+            for &word in &self.nodes[node].words {
+                found.push((position, word));
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_all() {
+        let automaton = Automaton::new(&["he", "she", "his", "hers"]);
+        let mut found = automaton.find_all("ushers".chars());
+        found.sort();
+        assert_eq!(found, vec![(3, 0), (3, 1), (5, 3)]);
+    }
+
+    #[test]
+    fn test_overlapping_and_empty() {
+        let automaton = Automaton::new(&["aa", "", "a"]);
+        let found = automaton.find_all("aaa".chars());
+        assert_eq!(found.iter().filter(|(_, w)| *w == 0).count(), 2);
+        assert_eq!(found.iter().filter(|(_, w)| *w == 2).count(), 3);
+        assert!(found.iter().all(|(_, w)| *w != 1));
+        assert_eq!(automaton.len(0), 2);
+    }
+}
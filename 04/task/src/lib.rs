@@ -0,0 +1,381 @@
+use aoc_common::{
+    day::{Day, Example, Part, Solution},
+    error::ParseError,
+    grid::Grid,
+    vec2::Vec2,
+};
+
+use automaton::Automaton;
+use template::Template;
+
+pub mod automaton;
+pub mod template;
+
+pub const DAY: Day = Day::new::<Day04>(aoc_common::default_input!());
+
+pub struct Day04;
+
+// The two crossing MAS of part 2
+pub const X_MAS: &str = "M.S/.A./M.S";
+
+pub struct Map {
+    grid: Grid<char>,
+}
+
+// A word found in the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    // Index into the list of words searched for
+    pub word: usize,
+    // Length of the word in chars
+    pub len: usize,
+    pub start: Vec2<i32>,
+    pub direction: Vec2<i32>,
+}
+
+impl Match {
+    // Compass name of the reading direction, e.g. `north-east`
+    pub fn direction_name(&self) -> &'static str {
+        const NAMES: [&str; 8] = [
+            "north",
+            "north-east",
+            "east",
+            "south-east",
+            "south",
+            "south-west",
+            "west",
+            "north-west",
+        ];
+        let index = Vec2::COMPASS.iter().position(|&d| d == self.direction);
+        index.map_or("?", |i| NAMES[i])
+    }
+
+    // The cells the word covers, from its first to its last char
+    pub fn cells(&self) -> impl Iterator<Item = Vec2<i32>> {
+        let (start, direction) = (self.start, self.direction);
+        (0..self.len as i32).map(move |i| start + direction * i)
+    }
+}
+
+impl Map {
+    // Every row has to have as many chars as the first, counted in chars not bytes
+    pub fn parse(input: &str) -> Result<Map, ParseError> {
+        Ok(Map {
+            grid: Grid::from_str(input, Some)?,
+        })
+    }
+
+    // Like `parse`, but ragged rows are filled up with `fill` to the longest row
+    pub fn parse_padded(input: &str, fill: char) -> Result<Map, ParseError> {
+        Ok(Map {
+            grid: Grid::from_str_padded(input, fill, Some)?,
+        })
+    }
+
+    // Every occurrence of `word` in any of the 8 directions
+    pub fn find_word(&self, word: &str) -> Vec<Match> {
+        self.find_words(&[word])
+    }
+
+    // Every occurrence of any of `words`, in row-major order of the start cell
+    //
+    // A word reading the same backwards, like `ABA`, is found twice on the same cells.
+    pub fn find_words(&self, words: &[&str]) -> Vec<Match> {
+        let words: Vec<Vec<char>> = words.iter().map(|w| w.chars().collect()).collect();
+        let mut matches = Vec::new();
+        for (start, _) in self.grid.iter() {
+            for direction in Vec2::COMPASS {
+                for (index, word) in words.iter().enumerate() {
+                    if self.reads(start, direction, word) {
+                        matches.push(Match {
+                            word: index,
+                            len: word.len(),
+                            start,
+                            direction,
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    // Same as `find_words`, but one automaton pass over every line of the grid
+    //
+    // Runs in time linear in the grid size for any number of words, so it suits
+    // dictionaries of thousands of words.
+    pub fn find_words_automaton(&self, words: &[&str]) -> Vec<Match> {
+        let automaton = Automaton::new(words);
+        let mut matches = Vec::new();
+        for direction in Vec2::COMPASS {
+            for line in self.grid.lines(direction) {
+                let line: Vec<(Vec2<i32>, char)> = line.map(|(p, &c)| (p, c)).collect();
+                for (end, word) in automaton.find_all(line.iter().map(|&(_, c)| c)) {
+                    let len = automaton.len(word);
+                    matches.push(Match {
+                        word,
+                        len,
+                        start: line[end + 1 - len].0,
+                        direction,
+                    });
+                }
+            }
+        }
+        // Same order as `find_words`
+        matches.sort_by_key(|m| {
+            let direction = Vec2::COMPASS.iter().position(|&d| d == m.direction);
+            (m.start.y, m.start.x, direction, m.word)
+        });
+        matches
+    }
+
+    // Whether `word` can be read from `start` in `direction`
+    fn reads(&self, start: Vec2<i32>, direction: Vec2<i32>, word: &[char]) -> bool {
+        !word.is_empty()
+            && self.grid.ray(start, direction).take(word.len()).count() == word.len()
+            && self
+                .grid
+                .ray(start, direction)
+                .zip(word)
+                .all(|((_, c), w)| c == w)
+    }
+
+    pub fn count_xmas(&self) -> i32 {
+        self.find_word("XMAS").len() as i32
+    }
+
+    // The grid with only the cells of `matches` shown and every other cell as `.`
+    //
+    // With `colour` each match gets its own ANSI colour, a cell shared by several
+    // matches takes the colour of the last one.
+    pub fn render(&self, matches: &[Match], colour: bool) -> String {
+        const COLOURS: [u8; 6] = [31, 32, 33, 34, 35, 36];
+        let mut owners = self.grid.map(|_| None);
+        for (i, found) in matches.iter().enumerate() {
+            for cell in found.cells() {
+                owners.set(cell, Some(i));
+            }
+        }
+        let mut out = String::new();
+        for (position, owner) in owners.iter() {
+            let c = self.grid.get(position).copied().unwrap_or('.');
+            match owner {
+                None => out.push('.'),
+                Some(i) if colour => {
+                    out += &format!("\x1b[{}m{}\x1b[0m", COLOURS[i % COLOURS.len()], c)
+                }
+                Some(_) => out.push(c),
+            }
+            if position.x as usize == self.grid.width() - 1 {
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    // Places where any rotation or reflection of `template` matches
+    //
+    // A place matching several distinct variants is counted once per variant.
+    pub fn count_template(&self, template: &Template) -> usize {
+        template
+            .variants()
+            .iter()
+            .map(|variant| variant.find(&self.grid).len())
+            .sum()
+    }
+}
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            part: Part::One,
+            input: include_str!("../example.txt"),
+            answer: "18",
+        },
+        Example {
+            part: Part::Two,
+            input: include_str!("../example.txt"),
+            answer: "9",
+        },
+    ];
+
+    type Input = Map;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Map, ParseError> {
+        Map::parse(input)
+    }
+
+    fn part1(map: &Map) -> i32 {
+        map.count_xmas()
+    }
+
+    fn part2(map: &Map) -> i32 {
+        map.count_template(&X_MAS.parse().unwrap()) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::day::check_examples;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_examples() {
+        check_examples::<Day04>();
+    }
+
+    fn example() -> Map {
+        Map::parse(include_str!("../example.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_find_word() {
+        let matches = example().find_word("XMAS");
+        assert_eq!(matches.len(), 18);
+        assert!(matches.contains(&Match {
+            word: 0,
+            len: 4,
+            start: Vec2::new(0, 4),
+            direction: Vec2::EAST,
+        }));
+        let last = matches.last().unwrap();
+        assert_eq!(
+            (last.start, last.direction),
+            (Vec2::new(9, 9), Vec2::NORTH_WEST)
+        );
+        assert_eq!(
+            last.cells().collect::<Vec<_>>(),
+            vec![
+                Vec2::new(9, 9),
+                Vec2::new(8, 8),
+                Vec2::new(7, 7),
+                Vec2::new(6, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_words() {
+        let map = example();
+        let matches = map.find_words(&["XMAS", "SAMX", "MAS"]);
+        let count = |word| matches.iter().filter(|m| m.word == word).count();
+        assert_eq!((count(0), count(1)), (18, 18));
+        assert_eq!(count(2), map.find_word("MAS").len());
+        assert!(map.find_word("").is_empty());
+        assert!(map.find_word("XMASXMASXMAS").is_empty());
+    }
+
+    #[test]
+    fn test_palindrome_found_twice() {
+        let map = Map::parse("ABA\n").unwrap();
+        let matches = map.find_word("ABA");
+        assert_eq!(matches.len(), 2);
+        let cells: HashSet<Vec<Vec2<i32>>> = matches
+            .iter()
+            .map(|m| {
+                let mut cells: Vec<_> = m.cells().collect();
+                cells.sort();
+                cells
+            })
+            .collect();
+        assert_eq!(cells.len(), 1);
+    }
+
+    #[test]
+    fn test_automaton_matches_find_words() {
+        let map = example();
+        let words = [
+            "XMAS",
+            "SAMX",
+            "MAS",
+            "A",
+            "MMMS",
+            "",
+            "XMASXMASXMAS",
+            "AMA",
+        ];
+        assert_eq!(map.find_words_automaton(&words), map.find_words(&words));
+        assert_eq!(map.find_words_automaton(&["XMAS"]).len(), 18);
+    }
+
+    #[test]
+    fn test_count_template() {
+        let map = example();
+        assert_eq!(map.count_template(&X_MAS.parse().unwrap()), 9);
+        // A straight template is the word search without the diagonals
+        let straight = map
+            .find_word("XMAS")
+            .iter()
+            .filter(|m| Vec2::CARDINALS.contains(&m.direction))
+            .count();
+        assert_eq!(map.count_template(&"XMAS".parse().unwrap()), straight);
+        assert_eq!(map.count_template(&"X/M/A/S".parse().unwrap()), straight);
+    }
+
+    #[test]
+    fn test_render() {
+        let map = example();
+        let expected = "\
+....XXMAS.
+.SAMXMS...
+...S..A...
+..A.A.MS.X
+XMASAMX.MM
+X.....XA.A
+S.S.S.S.SS
+.A.A.A.A.A
+..M.M.M.MM
+.X.X.XMASX
+";
+        assert_eq!(map.render(&map.find_word("XMAS"), false), expected);
+    }
+
+    #[test]
+    fn test_render_colour() {
+        let map = Map::parse("XMAS\nXMAS\n").unwrap();
+        let matches: Vec<Match> = (0..2)
+            .map(|y| Match {
+                word: 0,
+                len: 2,
+                start: Vec2::new(1, y),
+                direction: Vec2::EAST,
+            })
+            .collect();
+        assert_eq!(
+            map.render(&matches, true),
+            ".\x1b[31mM\x1b[0m\x1b[31mA\x1b[0m.\n.\x1b[32mM\x1b[0m\x1b[32mA\x1b[0m.\n"
+        );
+    }
+
+    #[test]
+    fn test_ragged_input() {
+        let input = "XMAS\nSAM\nXMAS\n";
+        let err = Map::parse(input).err().unwrap();
+        assert_eq!((err.line, err.text.as_str()), (2, "SAM"));
+        assert_eq!(err.message, "row 2 has 3 columns where row 1 has 4");
+
+        let map = Map::parse_padded(input, '.').unwrap();
+        assert_eq!(
+            map.render(&map.find_word("SAMX"), false),
+            "XMAS\n....\nXMAS\n"
+        );
+        assert_eq!(map.count_xmas(), 2);
+    }
+
+    #[test]
+    fn test_unicode_input() {
+        let map = Map::parse("ÄÖÜ\nöäü\n").unwrap();
+        let matches = map.find_word("Üü");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(
+            (matches[0].start, matches[0].direction),
+            (Vec2::new(2, 0), Vec2::SOUTH)
+        );
+        assert_eq!(map.find_words_automaton(&["Üü"]), matches);
+        assert_eq!(map.render(&matches, false), "..Ü\n..ü\n");
+    }
+}
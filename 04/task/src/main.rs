@@ -1,123 +1,92 @@
-use std::path::Path;
-
-struct Map {
-    width: usize,
-    height: usize,
-    map: Vec<Vec<char>>,
-}
-
-impl Map {
-    fn new(width: usize, height: usize) -> Map {
-        Map {
-            width,
-            height,
-            map: vec![vec!['.'; width]; height],
-        }
-    }
-
-    fn from_file(path: &Path) -> Map {
-        let contents = std::fs::read_to_string(path).unwrap();
-        let mut lines = contents.lines();
-        let width = lines.next().unwrap().len();
-        let height = contents.lines().count();
-        let mut map = Map::new(width, height);
-        for (y, line) in contents.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                map.map[y][x] = c;
+use aoc_common::{day::Solution, input::parse_input_with};
+use day04::{template::Template, Day04, Map};
+
+// Usage: day04 [--find WORD,WORD,...] [--automaton] [--highlight] [--colour]
+//              [--template ROWS] [--pad CHAR] [input]
+//
+// `--find` lists where each of the comma separated words occurs, `--automaton`
+// searches all of them in one pass, which is faster for many words.
+// `--highlight` draws the grid with only the found words, `--colour` colours each one,
+// both draw the puzzle's `XMAS` matches when no `--find` is given.
+// `--pad` fills up rows shorter than the longest one instead of rejecting them.
+// `--template` counts a 2D shape like `M.S/.A./M.S` in any rotation or reflection.
+fn main() {
+    let mut find = None;
+    let mut template = None;
+    let mut automaton = false;
+    let mut highlight = false;
+    let mut colour = false;
+    let mut pad = None;
+    let mut source = day04::DAY.input.source();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--find" => find = args.next(),
+            "--automaton" => automaton = true,
+            "--highlight" => highlight = true,
+            "--colour" => colour = true,
+            "--pad" => {
+                let fill = args.next().unwrap_or_default();
+                let mut chars = fill.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => pad = Some(c),
+                    _ => {
+                        eprintln!("error: --pad expects a single fill character");
+                        std::process::exit(1);
+                    }
+                }
             }
+            "--template" => template = args.next(),
+            _ => source = arg.parse().unwrap(),
         }
-        map
     }
-
-    fn get(&self, x: i32, y: i32) -> Option<char> {
-        if y < 0 || y >= self.height as i32 {
-            return None;
-        }
-        if x < 0 || x >= self.width as i32 {
-            return None;
+    let m = parse_input_with(&source, |input| match pad {
+        Some(fill) => Map::parse_padded(input, fill),
+        None => Day04::parse(input),
+    });
+
+    if let Some(template) = template {
+        match template.parse::<Template>() {
+            Err(why) => {
+                eprintln!("error: {}", why);
+                std::process::exit(1);
+            }
+            Ok(template) => println!("{}", m.count_template(&template)),
         }
-        return Some(self.map[y as usize][x as usize]);
+        return;
     }
 
-    fn test_star(&self, x: i32, y: i32) -> i32 {
-        return self.test(x, y, 1, 0)
-        + self.test(x, y, -1, 0)
-        + self.test(x, y, 0, 1)
-        + self.test(x, y, 0, -1) 
-        + self.test(x, y, 1, 1) 
-        + self.test(x, y, 1, -1)
-        + self.test(x, y, -1, -1) 
-        + self.test(x, y, -1, 1)
+    if highlight || colour {
+        find = find.or_else(|| Some("XMAS".to_string()));
     }
-
-    fn test(&self, x: i32, y: i32, dx: i32, dy: i32) -> i32 {
-        let candidate = [self.get(x, y), self.get(x + dx, y + dy), self.get(x + 2 * dx, y + 2 * dy), self.get(x + 3 * dx, y + 3 * dy)];
-        if candidate.iter().any(|&c| c == None) {
-            return 0;
-        }
-
-        if candidate.iter().map(|f| f.unwrap()).collect::<String>() == "XMAS" {
-            1
+    if let Some(words) = find {
+        let words: Vec<&str> = words.split(',').collect();
+        let matches = if automaton {
+            m.find_words_automaton(&words)
         } else {
-            0
+            m.find_words(&words)
+        };
+        if highlight || colour {
+            print!("{}", m.render(&matches, colour));
+            return;
         }
-    }
-    
-    fn count_xmas(&self) -> i32 {
-        let mut count = 0;
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.map[y][x] == 'X' {
-                    count += self.test_star(x as i32, y as i32);
-                }
-            }
+        for found in matches {
+            println!(
+                "{} at {},{} going {}",
+                words[found.word],
+                found.start.x,
+                found.start.y,
+                found.direction_name()
+            );
         }
-        count
+        return;
     }
 
-    fn test_cross_xmas(&self, x: i32, y: i32) -> bool {
-        let topr = self.get(x+1, y-1).unwrap_or('_');
-        let topl = self.get(x-1, y-1).unwrap_or('_');
-        let botr = self.get(x+1, y+1).unwrap_or('_');
-        let botl = self.get(x-1, y+1).unwrap_or('_');
-
-        if topr == 'M' && botl == 'S' && (topl == 'M' && botr == 'S' || topl == 'S' && botr == 'M') {
-            return true;
-        }
-
-        if topr == 'S' && botl == 'M' && (topl == 'M' && botr == 'S' || topl == 'S' && botr == 'M') {
-            return true;
-        }
-
-        false
-    }
-
-    fn count_cross_xmas(&self) -> i32 {
-        let mut count = 0;
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.map[y][x] == 'A' {
-                    if self.test_cross_xmas(x as i32, y as i32) {
-                        count += 1;
-                    }
-                }
-            }
-        }
-        count
-    }
-}
-
-fn main() {
-    let path = Path::new("input.txt");
-
-    let m = Map::from_file(&path);
-
     // --- Part One ---
-    let c = m.count_xmas();
     println!("Part One");
-    println!("XMAS count: {}", c);
+    println!("XMAS count: {}", Day04::part1(&m));
 
     // --- Part Two ---
     println!("Part Two");
-    println!("Cross XMAS count: {}", m.count_cross_xmas());
+    println!("Cross XMAS count: {}", Day04::part2(&m));
 }
@@ -0,0 +1,179 @@
+use aoc_common::{grid::Grid, vec2::Vec2};
+use std::{fmt, str::FromStr};
+
+// A small 2D shape to look for in the grid, `.` matching any char
+//
+// Written row by row, separated by `/` or newlines, e.g. `M.S/.A./M.S` for the X-MAS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    width: usize,
+    height: usize,
+    // Row by row, `None` for wildcards
+    cells: Vec<Option<char>>,
+}
+
+impl Template {
+    fn get(&self, x: usize, y: usize) -> Option<char> {
+        self.cells[y * self.width + x]
+    }
+
+    fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> Option<char>) -> Template {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Template {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // Turned by 90 degrees clockwise
+    pub fn rotate(&self) -> Template {
+        Template::from_fn(self.height, self.width, |x, y| {
+            self.get(y, self.height - 1 - x)
+        })
+    }
+
+    // Mirrored left to right
+    pub fn reflect(&self) -> Template {
+        Template::from_fn(self.width, self.height, |x, y| {
+            self.get(self.width - 1 - x, y)
+        })
+    }
+
+    // All distinct rotations and reflections, starting with the template itself
+    pub fn variants(&self) -> Vec<Template> {
+        let mut variants: Vec<Template> = Vec::with_capacity(8);
+        let mut current = self.clone();
+        for _ in 0..4 {
+            for variant in [current.clone(), current.reflect()] {
+                if !variants.contains(&variant) {
+                    variants.push(variant);
+                }
+            }
+            current = current.rotate();
+        }
+        variants
+    }
+
+    // Whether the template lies on the grid with its top left corner at `top_left`
+    pub fn matches_at(&self, grid: &Grid<char>, top_left: Vec2<i32>) -> bool {
+        (0..self.height).all(|y| {
+            (0..self.width).all(|x| {
+                let position = top_left + Vec2::new(x as i32, y as i32);
+                match (self.get(x, y), grid.get(position)) {
+                    (_, None) => false,
+                    (None, Some(_)) => true,
+                    (Some(want), Some(&c)) => want == c,
+                }
+            })
+        })
+    }
+
+    // Top left corners of every place the template itself matches
+    pub fn find(&self, grid: &Grid<char>) -> Vec<Vec2<i32>> {
+        grid.iter()
+            .map(|(position, _)| position)
+            .filter(|&position| self.matches_at(grid, position))
+            .collect()
+    }
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<Vec<char>> = s
+            .trim_end_matches('\n')
+            .split(['/', '\n'])
+            .map(|row| row.chars().collect())
+            .collect();
+        let width = rows[0].len();
+        if width == 0 {
+            return Err("empty template row 1".to_string());
+        }
+        if let Some(i) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "expected {} chars in template row {}, found {}",
+                width,
+                i + 1,
+                rows[i].len()
+            ));
+        }
+        let cells = rows
+            .iter()
+            .flatten()
+            .map(|&c| (c != '.').then_some(c))
+            .collect();
+        Ok(Template {
+            width,
+            height: rows.len(),
+            cells,
+        })
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                write!(f, "/")?;
+            }
+            for x in 0..self.width {
+                write!(f, "{}", self.get(x, y).unwrap_or('.'))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variants(template: &str) -> Vec<String> {
+        let template: Template = template.parse().unwrap();
+        template.variants().iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let template: Template = "M.S\n.A.\nM.S\n".parse().unwrap();
+        assert_eq!(template, "M.S/.A./M.S".parse().unwrap());
+        assert_eq!(template.to_string(), "M.S/.A./M.S");
+        assert_eq!(
+            "AB/C".parse::<Template>(),
+            Err("expected 2 chars in template row 2, found 1".to_string())
+        );
+        assert!("".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn test_variants() {
+        assert_eq!(
+            variants("M.S/.A./M.S"),
+            vec!["M.S/.A./M.S", "S.M/.A./S.M", "M.M/.A./S.S", "S.S/.A./M.M"]
+        );
+        assert_eq!(variants("XMAS").len(), 4);
+        assert_eq!(variants("AB/CD").len(), 8);
+        assert_eq!(variants("A").len(), 1);
+    }
+
+    #[test]
+    fn test_rotate_non_square() {
+        let template: Template = "AB/CD/EF".parse().unwrap();
+        assert_eq!(template.rotate().to_string(), "ECA/FDB");
+        assert_eq!(template.rotate().rotate().rotate().rotate(), template);
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::from_str("XAB\nYCD\n", Some).unwrap();
+        let template: Template = "A./.D".parse().unwrap();
+        assert_eq!(template.find(&grid), vec![Vec2::new(1, 0)]);
+        let template: Template = "AB/CD/..".parse().unwrap();
+        assert!(template.find(&grid).is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use aoc_common::{
+    day::{Day, Example, Part, Solution},
+    error::ParseError,
+    parse::lines,
+};
+use std::collections::HashMap;
+
+pub const DAY: Day = Day::new::<Day05>(aoc_common::default_input!());
+
+pub struct Day05;
+
+pub struct Task {
+    left_of: HashMap<i32, Vec<i32>>,
+    right_of: HashMap<i32, Vec<i32>>,
+    inputs: Vec<Vec<i32>>,
+}
+
+enum ReaderState {
+    Rules,
+    Inputs,
+}
+
+enum Direction {
+    Left,
+    Right,
+}
+
+impl Task {
+    pub fn parse(input: &str) -> Result<Task, ParseError> {
+        let mut left_of = HashMap::new();
+        let mut right_of = HashMap::new();
+        let mut inputs = Vec::new();
+        let mut state = ReaderState::Rules;
+        for line in lines(input) {
+            if line.text.is_empty() {
+                state = ReaderState::Inputs;
+                continue;
+            }
+            match state {
+                ReaderState::Rules => {
+                    let (left, right) = line.split_once("|")?;
+                    let left: i32 = line.parse(left)?;
+                    let right: i32 = line.parse(right)?;
+                    left_of.entry(left).or_insert(Vec::new()).push(right);
+                    right_of.entry(right).or_insert(Vec::new()).push(left);
+                }
+                ReaderState::Inputs => {
+                    inputs.push(line.numbers(",")?);
+                }
+            }
+        }
+        Ok(Task {
+            left_of,
+            right_of,
+            inputs,
+        })
+    }
+
+    fn is_valid(&self, i: i32, dir: Direction, j: i32) -> bool {
+        let rules = match dir {
+            Direction::Left => self.right_of.get(&i),
+            Direction::Right => self.left_of.get(&i),
+        };
+        match rules {
+            Some(rules) => rules.contains(&j),
+            None => false,
+        }
+    }
+
+    pub fn solve_part_one(&self) -> i32 {
+        let mut result = 0;
+        for input in &self.inputs {
+            if self.input_valid(input) {
+                result += input[input.len() / 2];
+            }
+        }
+        result
+    }
+
+    fn input_valid(&self, input: &[i32]) -> bool {
+        let mut input_valid = true;
+        for (i, &number) in input.iter().enumerate() {
+            let mut valid = true;
+            for &left in &input[..i] {
+                valid = valid && self.is_valid(number, Direction::Left, left);
+            }
+            for &right in &input[i + 1..] {
+                valid = valid && self.is_valid(number, Direction::Right, right);
+            }
+            input_valid = input_valid && valid;
+        }
+        input_valid
+    }
+
+    fn try_sort(&self, mut cur_solution: Vec<i32>, mut open: Vec<i32>) -> Option<Vec<i32>> {
+        // If our current solution is empty, we just take the first element from the open list
+        if cur_solution.is_empty() {
+            cur_solution.push(open[0]);
+            open.remove(0);
+            return self.try_sort(cur_solution, open);
+        }
+
+        // If the open list is empty, we have a solution
+        if open.is_empty() {
+            return Some(cur_solution);
+        }
+
+        // We try to insert the first element from the open list at every possible position
+        let i = 0;
+        // We try to insert the element at every possible position
+        for j in 0..cur_solution.len() + 1 {
+            let mut new_solution = cur_solution.clone();
+            new_solution.insert(j, open[i]);
+            let mut new_open = open.clone();
+            new_open.remove(i);
+
+            if !self.input_valid(&new_solution) {
+                continue;
+            }
+
+            if let Some(solution) = self.try_sort(new_solution, new_open) {
+                return Some(solution);
+            }
+        }
+        None
+    }
+
+    pub fn solve_part_two(&self) -> i32 {
+        let mut incorectly_sorted_inputs = Vec::new();
+
+        for input in &self.inputs {
+            if !self.input_valid(input) {
+                incorectly_sorted_inputs.push(input.clone());
+            }
+        }
+
+        let mut result = 0;
+        for input in incorectly_sorted_inputs {
+            let solution = self.try_sort(Vec::new(), input);
+            if let Some(solution) = solution {
+                result += solution[solution.len() / 2];
+            }
+        }
+        result
+    }
+}
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            part: Part::One,
+            input: include_str!("../example.txt"),
+            answer: "143",
+        },
+        Example {
+            part: Part::Two,
+            input: include_str!("../example.txt"),
+            answer: "123",
+        },
+    ];
+
+    type Input = Task;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Task, ParseError> {
+        Task::parse(input)
+    }
+
+    fn part1(task: &Task) -> i32 {
+        task.solve_part_one()
+    }
+
+    fn part2(task: &Task) -> i32 {
+        task.solve_part_two()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::day::check_examples;
+
+    #[test]
+    fn test_examples() {
+        check_examples::<Day05>();
+    }
+}
@@ -0,0 +1,305 @@
+use aoc_common::{
+    day::{Day, Example, Part, Solution},
+    error::ParseError,
+    grid::Grid,
+    vec2::Vec2,
+};
+use core::fmt;
+use std::collections::HashSet;
+
+pub const DAY: Day = Day::new::<Day06>(aoc_common::default_input!());
+
+pub struct Day06;
+
+#[derive(Clone, Copy, PartialEq, Hash, Eq)]
+enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Clone)]
+struct Guard {
+    position: Vec2<i32>,
+    direction: Vec2<i32>,
+}
+
+impl Guard {
+    fn step(&mut self, map: &Map) {
+        let next_position = self.position + self.direction;
+        if map.tile_at(&next_position).is_empty() {
+            self.position = next_position;
+        } else {
+            self.direction = self.direction.rotate_right();
+            self.step(map);
+        }
+    }
+
+    fn orientation(&self) -> Orientation {
+        if self.direction.x == 0 {
+            Orientation::Vertical
+        } else {
+            Orientation::Horizontal
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Tile {
+    Empty,
+    EmptyButVisited(Vec<Orientation>),
+    Occupied,
+    OccupiedInserted,
+}
+
+impl Tile {
+    fn is_empty(&self) -> bool {
+        matches!(self, Tile::Empty | Tile::EmptyButVisited(_))
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tile::Empty => write!(f, "."),
+            Tile::EmptyButVisited(orientations) => {
+                if orientations.contains(&Orientation::Horizontal)
+                    && orientations.contains(&Orientation::Vertical)
+                {
+                    write!(f, "+")
+                } else if orientations.contains(&Orientation::Horizontal) {
+                    write!(f, "-")
+                } else {
+                    write!(f, "|")
+                }
+            }
+            Tile::Occupied => {
+                write!(f, "#")
+            }
+            Tile::OccupiedInserted => {
+                write!(f, "O")
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Map {
+    tiles: Grid<Tile>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct LoopGuard {
+    position: Vec2<i32>,
+    direction: Vec2<i32>,
+}
+
+impl Map {
+    fn tile_at(&self, position: &Vec2<i32>) -> &Tile {
+        // if oob, return empty
+        self.tiles.get(*position).unwrap_or(&Tile::Empty)
+    }
+
+    fn set_tile(&mut self, position: &Vec2<i32>, tile: Tile) {
+        // if oob, do nothing
+        self.tiles.set(*position, tile);
+    }
+}
+
+#[derive(Clone)]
+pub struct Simulation {
+    map: Map,
+    guard: Guard,
+    guard_starting_position: Vec2<i32>,
+}
+
+impl Simulation {
+    pub fn parse(input: &str) -> Result<Simulation, ParseError> {
+        let chars = Grid::from_str(input, |c| ".#^v<>".contains(c).then_some(c))?;
+        let guard = chars
+            .iter()
+            .find_map(|(position, &c)| {
+                let direction = match c {
+                    '^' => Vec2::NORTH,
+                    'v' => Vec2::SOUTH,
+                    '<' => Vec2::WEST,
+                    '>' => Vec2::EAST,
+                    _ => return None,
+                };
+                Some(Guard {
+                    position,
+                    direction,
+                })
+            })
+            .ok_or(ParseError::new(1, 1, "", "missing guard"))?;
+        let tiles = chars.map(|&c| match c {
+            '#' => Tile::Occupied,
+            _ => Tile::Empty,
+        });
+        Ok(Simulation {
+            map: Map { tiles },
+            guard_starting_position: guard.position,
+            guard,
+        })
+    }
+
+    fn step(&mut self) {
+        let tile = self.map.tile_at(&self.guard.position);
+        match tile {
+            Tile::EmptyButVisited(orientations) => {
+                if !orientations.contains(&self.guard.orientation()) {
+                    let mut new_orientations = orientations.clone();
+                    new_orientations.push(self.guard.orientation());
+                    self.map.set_tile(
+                        &self.guard.position,
+                        Tile::EmptyButVisited(new_orientations),
+                    );
+                }
+            }
+            _ => {
+                self.map.set_tile(
+                    &self.guard.position,
+                    Tile::EmptyButVisited(vec![self.guard.orientation()]),
+                );
+            }
+        }
+        self.guard.step(&self.map);
+    }
+
+    fn run_until_guard_oob(&mut self) -> Vec<LoopGuard> {
+        let mut path: Vec<LoopGuard> = vec![];
+        loop {
+            if !self.map.tiles.in_bounds(self.guard.position) {
+                return path;
+            }
+            path.push(LoopGuard {
+                position: self.guard.position,
+                direction: self.guard.direction,
+            });
+            self.step();
+        }
+    }
+
+    fn run_until_loop(&mut self, mut previous_positions: HashSet<LoopGuard>) -> bool {
+        loop {
+            previous_positions.insert(LoopGuard {
+                position: self.guard.position,
+                direction: self.guard.direction,
+            });
+            self.step();
+            if !self.map.tiles.in_bounds(self.guard.position) {
+                return false;
+            }
+            if previous_positions.contains(&LoopGuard {
+                position: self.guard.position,
+                direction: self.guard.direction,
+            }) {
+                return true;
+            }
+        }
+    }
+
+    // Walk a copy of the simulation until the guard leaves and count the visited tiles
+    pub fn visited_until_oob(&self) -> usize {
+        let mut visited_sim = self.clone();
+        visited_sim.run_until_guard_oob();
+        visited_sim.count_visited()
+    }
+
+    // At every step of the path, we insert an obstacle and try to find a loop
+    pub fn loop_positions(&self) -> HashSet<Vec2<i32>> {
+        let path = self.clone().run_until_guard_oob();
+        let (tx, rx) = std::sync::mpsc::channel();
+        for g in path {
+            if g.position == self.guard_starting_position {
+                continue;
+            }
+            let tx = tx.clone();
+            let mut test_sim = self.clone();
+            std::thread::spawn(move || {
+                test_sim.map.set_tile(&g.position, Tile::OccupiedInserted);
+                if test_sim.run_until_loop(HashSet::new()) {
+                    tx.send(g.position).unwrap();
+                }
+            });
+        }
+        drop(tx);
+        rx.into_iter().collect()
+    }
+
+    fn count_visited(&self) -> usize {
+        self.map
+            .tiles
+            .iter()
+            .filter(|(_, tile)| matches!(tile, Tile::EmptyButVisited(_)))
+            .count()
+    }
+}
+
+impl fmt::Display for Simulation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (position, tile) in self.map.tiles.iter() {
+            if position == self.guard.position {
+                write!(
+                    f,
+                    "{}",
+                    match self.guard.direction {
+                        Vec2::NORTH => '^',
+                        Vec2::SOUTH => 'v',
+                        Vec2::WEST => '<',
+                        Vec2::EAST => '>',
+                        _ => panic!("Invalid direction"),
+                    }
+                )?;
+            } else {
+                write!(f, "{}", tile)?;
+            }
+            if position.x as usize == self.map.tiles.width() - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            part: Part::One,
+            input: include_str!("../example.txt"),
+            answer: "41",
+        },
+        Example {
+            part: Part::Two,
+            input: include_str!("../example.txt"),
+            answer: "6",
+        },
+    ];
+
+    type Input = Simulation;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Simulation, ParseError> {
+        Simulation::parse(input)
+    }
+
+    fn part1(sim: &Simulation) -> usize {
+        sim.visited_until_oob()
+    }
+
+    fn part2(sim: &Simulation) -> usize {
+        sim.loop_positions().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::day::check_examples;
+
+    #[test]
+    fn test_examples() {
+        check_examples::<Day06>();
+    }
+}
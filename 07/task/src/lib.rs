@@ -0,0 +1,248 @@
+use aoc_common::{
+    day::{Day, Example, Part, Solution},
+    error::ParseError,
+    parse::lines,
+};
+use std::thread;
+
+pub const DAY: Day = Day::new::<Day07>(aoc_common::default_input!());
+
+pub struct Day07;
+
+#[derive(Copy, Clone)]
+enum Operation {
+    Add,
+    Multiply,
+    Concat,
+}
+
+const PART_ONE_OPERATIONS: [Operation; 2] = [Operation::Multiply, Operation::Add];
+const PART_TWO_OPERATIONS: [Operation; 3] =
+    [Operation::Multiply, Operation::Add, Operation::Concat];
+
+#[derive(Copy, Clone)]
+enum Value {
+    Number(i128),
+    Operation(Operation),
+}
+
+#[derive(Clone)]
+struct Expression {
+    values: Vec<Value>,
+}
+
+impl Expression {
+    fn new() -> Expression {
+        Expression { values: Vec::new() }
+    }
+
+    fn evaluate(&self) -> i128 {
+        let mut result = 0;
+        let mut operation = Operation::Add;
+        for value in &self.values {
+            match value {
+                Value::Number(n) => match operation {
+                    Operation::Add => result += n,
+                    Operation::Multiply => result *= n,
+                    Operation::Concat => {
+                        let result_str = result.to_string();
+                        let n_str = n.to_string();
+                        result = format!("{}{}", result_str, n_str).parse::<i128>().unwrap();
+                    }
+                },
+                Value::Operation(op) => {
+                    operation = *op;
+                }
+            }
+        }
+        result
+    }
+}
+
+fn solve(result: i128, expr: Expression, open: Vec<i128>, operations: &[Operation]) -> bool {
+    if expr.evaluate() > result {
+        return false;
+    }
+
+    if expr.evaluate() == result && open.is_empty() {
+        return true;
+    }
+
+    if open.is_empty() {
+        return false;
+    }
+
+    for &op in operations {
+        let mut new_expr = expr.clone();
+        let mut open = open.clone();
+        new_expr.values.push(Value::Number(open[0]));
+        open.remove(0);
+        new_expr.values.push(Value::Operation(op));
+        if solve(result, new_expr, open, operations) {
+            return true;
+        }
+    }
+
+    false
+}
+
+// One line of the input: the test value and the numbers to combine
+#[derive(Clone)]
+pub struct Equation {
+    result: i128,
+    inputs: Vec<i128>,
+}
+
+// Sum the results of all equations that can be solved with the given operations
+fn calibration_sum(equations: &[Equation], operations: &'static [Operation]) -> i128 {
+    let mut sum = 0;
+    let (tx, rx) = std::sync::mpsc::channel();
+    for equation in equations {
+        let Equation { result, inputs } = equation.clone();
+
+        let tx = tx.clone();
+        thread::spawn(move || {
+            if solve(result, Expression::new(), inputs, operations) {
+                tx.send(result).unwrap();
+            }
+        });
+    }
+    drop(tx);
+    for r in rx {
+        sum += r;
+    }
+    sum
+}
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            part: Part::One,
+            input: include_str!("../example.txt"),
+            answer: "3749",
+        },
+        Example {
+            part: Part::Two,
+            input: include_str!("../example.txt"),
+            answer: "11387",
+        },
+    ];
+
+    type Input = Vec<Equation>;
+    type Part1 = i128;
+    type Part2 = i128;
+
+    fn parse(input: &str) -> Result<Vec<Equation>, ParseError> {
+        lines(input)
+            .map(|line| {
+                let (result, inputs) = line.split_once(": ")?;
+                Ok(Equation {
+                    result: line.parse(result)?,
+                    inputs: inputs
+                        .split(' ')
+                        .map(|x| line.parse(x))
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .collect()
+    }
+
+    fn part1(equations: &Vec<Equation>) -> i128 {
+        calibration_sum(equations, &PART_ONE_OPERATIONS)
+    }
+
+    fn part2(equations: &Vec<Equation>) -> i128 {
+        calibration_sum(equations, &PART_TWO_OPERATIONS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::day::check_examples;
+
+    #[test]
+    fn test_examples() {
+        check_examples::<Day07>();
+    }
+
+    #[test]
+    fn test_expression() {
+        let mut expression = Expression::new();
+        expression.values.push(Value::Number(1));
+        expression.values.push(Value::Operation(Operation::Add));
+        expression.values.push(Value::Number(2));
+        expression
+            .values
+            .push(Value::Operation(Operation::Multiply));
+        expression.values.push(Value::Number(3));
+        assert_eq!(expression.evaluate(), 9);
+    }
+
+    #[test]
+    fn test_expression2() {
+        let mut expression = Expression::new();
+        expression.values.push(Value::Number(1));
+        expression.values.push(Value::Operation(Operation::Add));
+        expression.values.push(Value::Number(2));
+        expression.values.push(Value::Operation(Operation::Add));
+        expression.values.push(Value::Number(3));
+        assert_eq!(expression.evaluate(), 6);
+    }
+
+    #[test]
+    fn test_expression3() {
+        let expression = Expression {
+            values: vec![
+                Value::Number(10),
+                Value::Operation(Operation::Multiply),
+                Value::Number(19),
+                Value::Operation(Operation::Add),
+                Value::Number(3),
+            ],
+        };
+        assert_eq!(expression.evaluate(), 193);
+    }
+
+    #[test]
+    fn test_expression4() {
+        let expression = Expression {
+            values: vec![
+                Value::Number(10),
+                Value::Operation(Operation::Multiply),
+                Value::Number(19),
+                Value::Operation(Operation::Add),
+            ],
+        };
+        assert_eq!(expression.evaluate(), 190);
+    }
+
+    #[test]
+    fn test_expression_concat() {
+        let expression = Expression {
+            values: vec![
+                Value::Number(8),
+                Value::Operation(Operation::Concat),
+                Value::Number(6),
+            ],
+        };
+        assert_eq!(expression.evaluate(), 86);
+    }
+
+    #[test]
+    fn test_expression5() {
+        let expression = Expression {
+            values: vec![
+                Value::Number(6),
+                Value::Operation(Operation::Multiply),
+                Value::Number(8),
+                Value::Operation(Operation::Concat),
+                Value::Number(6),
+                Value::Operation(Operation::Multiply),
+                Value::Number(15),
+            ],
+        };
+        assert_eq!(expression.evaluate(), 7290);
+    }
+}
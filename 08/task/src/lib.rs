@@ -0,0 +1,185 @@
+use aoc_common::{
+    day::{Day, Example, Part, Solution},
+    error::ParseError,
+    grid::Grid,
+    vec2::Vec2,
+};
+use itertools::Itertools;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+pub const DAY: Day = Day::new::<Day08>(aoc_common::default_input!());
+
+pub struct Day08;
+
+#[derive(Clone)]
+struct Antenna {
+    position: Vec2<i32>,
+}
+
+impl Antenna {
+    fn new(position: Vec2<i32>) -> Self {
+        Antenna { position }
+    }
+}
+
+#[derive(Clone)]
+pub struct Map {
+    grid: Grid<char>,
+    antennas: HashMap<char, Vec<Antenna>>,
+    antinodes: HashSet<Vec2<i32>>,
+}
+
+impl Map {
+    // Read the map from the puzzle input
+    // Every non . char is an antenna where the char is the frequency
+    pub fn parse(content: &str) -> Result<Self, ParseError> {
+        let grid = Grid::from_str(content, Some)?;
+        let mut antennas = HashMap::new();
+        for (position, &c) in grid.iter() {
+            if c != '.' {
+                antennas
+                    .entry(c)
+                    .or_insert(Vec::new())
+                    .push(Antenna::new(position));
+            }
+        }
+        Ok(Map {
+            grid,
+            antennas,
+            antinodes: HashSet::new(),
+        })
+    }
+
+    // Calculate the antinodes
+    pub fn calculate_antinodes(&mut self) {
+        for antennas in self.antennas.values() {
+            for pairs in antennas.iter().combinations(2) {
+                let a = pairs[0];
+                let b = pairs[1];
+                let delta = a.position - b.position;
+                let antinode = b.position - delta;
+                let other_antinode = a.position + delta;
+                self.antinodes.insert(antinode);
+                self.antinodes.insert(other_antinode);
+            }
+        }
+    }
+
+    // Calculate the antinodes
+    pub fn calculate_antinodes_part_2(&mut self) {
+        for antennas in self.antennas.values() {
+            for pairs in antennas.iter().combinations(2) {
+                let a = pairs[0];
+                let b = pairs[1];
+                let delta = a.position - b.position;
+                let mut antinode = b.position - delta;
+                while self.in_bounds(&antinode) {
+                    self.antinodes.insert(antinode);
+                    antinode -= delta;
+                }
+                let mut other_antinode = a.position + delta;
+                while self.in_bounds(&other_antinode) {
+                    self.antinodes.insert(other_antinode);
+                    other_antinode += delta;
+                }
+            }
+        }
+        for antennas in self.antennas.values() {
+            if antennas.len() > 1 {
+                for antenna in antennas {
+                    self.antinodes.insert(antenna.position);
+                }
+            }
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    fn in_bounds(&self, position: &Vec2<i32>) -> bool {
+        self.grid.in_bounds(*position)
+    }
+
+    pub fn antinodes_within_bounds(&self) -> i32 {
+        let mut sum = 0;
+        for antinode in &self.antinodes {
+            if self.in_bounds(antinode) {
+                sum += 1;
+            }
+        }
+        sum
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (position, &c) in self.grid.iter() {
+            if c != '.' {
+                write!(f, "{}", c)?;
+            } else if self.antinodes.contains(&position) {
+                write!(f, "#")?;
+            } else {
+                write!(f, ".")?;
+            }
+            if position.x as usize == self.grid.width() - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            part: Part::One,
+            input: include_str!("../example.txt"),
+            answer: "14",
+        },
+        Example {
+            part: Part::Two,
+            input: include_str!("../example.txt"),
+            answer: "34",
+        },
+    ];
+
+    type Input = Map;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Map, ParseError> {
+        Map::parse(input)
+    }
+
+    fn part1(map: &Map) -> i32 {
+        let mut map = map.clone();
+        map.calculate_antinodes();
+        map.antinodes_within_bounds()
+    }
+
+    fn part2(map: &Map) -> i32 {
+        let mut map = map.clone();
+        map.calculate_antinodes_part_2();
+        map.antinodes_within_bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::day::check_examples;
+
+    #[test]
+    fn test_examples() {
+        check_examples::<Day08>();
+    }
+}
@@ -0,0 +1,138 @@
+use aoc_common::bench::{Stats, Timings};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct StageBaseline {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayBaseline {
+    pub runs: usize,
+    pub parse: StageBaseline,
+    pub part1: StageBaseline,
+    pub part2: StageBaseline,
+}
+
+// Timings of earlier runs, keyed by day
+pub type Baseline = BTreeMap<u8, DayBaseline>;
+
+impl From<Stats> for StageBaseline {
+    fn from(stats: Stats) -> Self {
+        StageBaseline {
+            min_ns: stats.min.as_nanos() as u64,
+            median_ns: stats.median.as_nanos() as u64,
+            p95_ns: stats.p95.as_nanos() as u64,
+        }
+    }
+}
+
+impl DayBaseline {
+    pub fn new(timings: &Timings, runs: usize) -> DayBaseline {
+        DayBaseline {
+            runs,
+            parse: timings.parse.into(),
+            part1: timings.part1.into(),
+            part2: timings.part2.into(),
+        }
+    }
+}
+
+pub fn load(path: &Path) -> Result<Baseline, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|why| format!("couldn't read {}: {}", path.display(), why))?;
+    serde_json::from_str(&contents)
+        .map_err(|why| format!("invalid baseline {}: {}", path.display(), why))
+}
+
+// Store the timings of one day, keeping the other days already in the file
+pub fn save(path: &Path, day: u8, baseline: DayBaseline) -> Result<(), String> {
+    let mut all = if path.exists() {
+        load(path)?
+    } else {
+        Baseline::new()
+    };
+    all.insert(day, baseline);
+    let json = serde_json::to_string_pretty(&all).unwrap();
+    std::fs::write(path, json + "\n")
+        .map_err(|why| format!("couldn't write {}: {}", path.display(), why))
+}
+
+// Relative change of the median against the baseline, e.g. 0.25 for 25% slower
+fn change(stats: &Stats, baseline: &StageBaseline) -> f64 {
+    let median = stats.median.as_nanos() as f64;
+    let base = baseline.median_ns.max(1) as f64;
+    median / base - 1.0
+}
+
+// Print the timing table and return the number of stages slower than `threshold` percent
+pub fn report(timings: &Timings, baseline: Option<&DayBaseline>, threshold: f64) -> usize {
+    let stages = [
+        ("parse", &timings.parse, baseline.map(|b| &b.parse)),
+        ("part1", &timings.part1, baseline.map(|b| &b.part1)),
+        ("part2", &timings.part2, baseline.map(|b| &b.part2)),
+    ];
+
+    let mut regressions = 0;
+    println!(
+        "{:<6} {:>12} {:>12} {:>12} {:>12}",
+        "stage", "min", "median", "p95", "baseline"
+    );
+    for (name, stats, base) in stages {
+        print!(
+            "{:<6} {:>12.2?} {:>12.2?} {:>12.2?}",
+            name, stats.min, stats.median, stats.p95
+        );
+        if let Some(base) = base {
+            let change = change(stats, base);
+            let verdict = if change * 100.0 > threshold {
+                regressions += 1;
+                "REGRESSION"
+            } else {
+                "ok"
+            };
+            print!(
+                " {:>12.2?} {:+7.1}% {}",
+                Duration::from_nanos(base.median_ns),
+                change * 100.0,
+                verdict
+            );
+        }
+        println!();
+    }
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change() {
+        let stats = Stats::from_samples(vec![Duration::from_nanos(150)]);
+        let base = StageBaseline {
+            min_ns: 90,
+            median_ns: 100,
+            p95_ns: 120,
+        };
+        assert!((change(&stats, &base) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let stats = Stats::from_samples(vec![Duration::from_micros(3)]);
+        let timings = Timings {
+            parse: stats,
+            part1: stats,
+            part2: stats,
+        };
+        let mut all = Baseline::new();
+        all.insert(6, DayBaseline::new(&timings, 1));
+        let json = serde_json::to_string(&all).unwrap();
+        let loaded: Baseline = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded[&6].part2.median_ns, 3000);
+    }
+}
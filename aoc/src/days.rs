@@ -0,0 +1,44 @@
+use aoc_common::day::Day;
+
+// Every solved day of the calendar, in order
+pub const DAYS: [Day; 8] = [
+    day01::DAY,
+    day02::DAY,
+    day03::DAY,
+    day04::DAY,
+    day05::DAY,
+    day06::DAY,
+    day07::DAY,
+    day08::DAY,
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::day::Part;
+
+    #[test]
+    fn test_days_in_order() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.number as usize, i + 1);
+        }
+    }
+
+    #[test]
+    fn test_every_day_has_examples() {
+        for day in &DAYS {
+            for part in Part::both() {
+                assert!(
+                    day.examples.iter().any(|example| example.part == part),
+                    "day {} has no example for part {}",
+                    day.number,
+                    part
+                );
+            }
+        }
+    }
+}
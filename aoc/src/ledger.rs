@@ -0,0 +1,136 @@
+use aoc_common::{day::Part, error::ParseError, parse::lines};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+// Ledger kept at the root of the workspace unless another one is given
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.txt");
+
+// FNV-1a, stable across platforms and compiler versions unlike the std hasher
+pub fn hash_input(input: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(String),
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong(expected) => write!(f, "WRONG, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+// Accepted answers per day, part and input hash
+//
+// Every line of the file is `<day> <part> <input hash> <answer>`, lines starting with `#` are comments.
+#[derive(Debug)]
+pub struct Ledger {
+    path: PathBuf,
+    answers: BTreeMap<(u8, Part, u64), String>,
+}
+
+impl Ledger {
+    pub fn parse(path: &Path, contents: &str) -> Result<Ledger, ParseError> {
+        let mut answers = BTreeMap::new();
+        for line in lines(contents) {
+            if line.text.trim().is_empty() || line.text.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.text.splitn(4, ' ');
+            let (Some(day), Some(part), Some(hash), Some(answer)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(line.error_at_end("expected `<day> <part> <input hash> <answer>`"));
+            };
+            let day = line.parse(day)?;
+            let part = line.parse(part)?;
+            let hash = u64::from_str_radix(hash, 16)
+                .map_err(|_| line.error(hash, "invalid input hash"))?;
+            answers.insert((day, part, hash), answer.to_string());
+        }
+        Ok(Ledger {
+            path: path.to_path_buf(),
+            answers,
+        })
+    }
+
+    // Load the ledger, starting an empty one if the file does not exist yet
+    pub fn load(path: &Path) -> Result<Ledger, String> {
+        if !path.exists() {
+            return Ledger::parse(path, "").map_err(|err| err.to_string());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|why| format!("error: couldn't read {}: {}\n", path.display(), why))?;
+        Ledger::parse(path, &contents).map_err(|err| err.with_file(path).render(&contents))
+    }
+
+    pub fn verdict(&self, day: u8, part: Part, hash: u64, answer: &str) -> Verdict {
+        match self.answers.get(&(day, part, hash)) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong(expected.clone()),
+        }
+    }
+
+    pub fn accept(&mut self, day: u8, part: Part, hash: u64, answer: &str) {
+        self.answers.insert((day, part, hash), answer.to_string());
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let mut contents = String::from("# day part input-hash answer\n");
+        for ((day, part, hash), answer) in &self.answers {
+            contents += &format!("{} {} {:016x} {}\n", day, part, hash, answer);
+        }
+        std::fs::write(&self.path, contents)
+            .map_err(|why| format!("error: couldn't write {}: {}\n", self.path.display(), why))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_input() {
+        assert_eq!(hash_input(""), 0xcbf29ce484222325);
+        assert_eq!(hash_input("a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(hash_input("3   4\n"), hash_input("3   4"));
+    }
+
+    #[test]
+    fn test_verdict() {
+        let ledger = Ledger::parse(
+            Path::new("answers.txt"),
+            "# day part input-hash answer\n6 2 00000000000000ff 2162\n",
+        )
+        .unwrap();
+        assert_eq!(ledger.verdict(6, Part::Two, 0xff, "2162"), Verdict::Correct);
+        assert_eq!(
+            ledger.verdict(6, Part::Two, 0xff, "2161"),
+            Verdict::Wrong("2162".to_string())
+        );
+        assert_eq!(ledger.verdict(6, Part::One, 0xff, "5329"), Verdict::Unknown);
+        assert_eq!(ledger.verdict(6, Part::Two, 0xfe, "2162"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Ledger::parse(Path::new("answers.txt"), "6 2 zz 2162\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 5, "zz"));
+    }
+}
@@ -0,0 +1,199 @@
+use aoc_common::{
+    day::{Day, Part},
+    input::Source,
+};
+use clap::{Args, Parser, Subcommand};
+use ledger::{Ledger, Verdict};
+use std::path::PathBuf;
+
+mod bench;
+mod days;
+mod ledger;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2024 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day or the whole calendar
+    Run(RunArgs),
+    /// Time parsing and both parts of a day over repeated runs
+    Bench(BenchArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to run
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Run every day of the calendar
+    #[arg(long)]
+    all: bool,
+
+    /// Only run this part (1 or 2), both parts otherwise
+    #[arg(long)]
+    part: Option<Part>,
+
+    /// Puzzle input file or `-` for stdin, defaults to the day's input.txt
+    #[arg(long, conflicts_with = "all")]
+    input: Option<Source>,
+
+    /// Ledger of accepted answers to check the results against
+    #[arg(long, default_value = ledger::DEFAULT_PATH)]
+    ledger: PathBuf,
+
+    /// Record the answers of this run as correct in the ledger
+    #[arg(long)]
+    accept: bool,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Day to benchmark
+    #[arg(long)]
+    day: u8,
+
+    /// Number of repetitions
+    #[arg(
+        long,
+        default_value_t = 10,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    runs: usize,
+
+    /// Puzzle input file or `-` for stdin, defaults to the day's input.txt
+    #[arg(long)]
+    input: Option<Source>,
+
+    /// Compare against the timings stored in this JSON file
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Store the timings in this JSON file for later comparison
+    #[arg(long)]
+    save_baseline: Option<PathBuf>,
+
+    /// Percentage the median may grow over the baseline before it is flagged
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+fn read_source(source: &Source) -> Result<String, String> {
+    source.read().map_err(|why| format!("error: {}\n", why))
+}
+
+// Solve the requested parts of a day, rendering parse errors against the input
+//
+// Returns the number of answers the ledger knows to be wrong.
+fn run_day(
+    day: &Day,
+    parts: &[Part],
+    source: &Source,
+    ledger: &mut Ledger,
+    accept: bool,
+) -> Result<usize, String> {
+    let input = read_source(source)?;
+    let hash = ledger::hash_input(&input);
+    let answers = day
+        .run(&input, parts)
+        .map_err(|err| err.with_file(source.to_string()).render(&input))?;
+
+    let mut wrong = 0;
+    for (&part, answer) in parts.iter().zip(answers) {
+        if accept {
+            ledger.accept(day.number, part, hash, &answer);
+        }
+        let verdict = ledger.verdict(day.number, part, hash, &answer);
+        if let Verdict::Wrong(_) = verdict {
+            wrong += 1;
+        }
+        println!(
+            "Day {:02} part {}: {} ({})",
+            day.number, part, answer, verdict
+        );
+    }
+    Ok(wrong)
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::both().to_vec(),
+    };
+
+    let mut ledger = Ledger::load(&args.ledger)?;
+    let mut wrong = 0;
+    if args.all {
+        for day in &days::DAYS {
+            let source = day.input.source();
+            wrong += run_day(day, &parts, &source, &mut ledger, args.accept)?;
+        }
+    } else {
+        let number = args.day.unwrap();
+        let day = days::find(number).ok_or(format!("error: day {} is not solved yet\n", number))?;
+        let source = args.input.unwrap_or_else(|| day.input.source());
+        wrong += run_day(day, &parts, &source, &mut ledger, args.accept)?;
+    }
+
+    if args.accept {
+        ledger.save()?;
+    }
+    if wrong > 0 {
+        return Err(format!(
+            "error: {} answer(s) differ from the ledger\n",
+            wrong
+        ));
+    }
+    Ok(())
+}
+
+fn bench(args: BenchArgs) -> Result<(), String> {
+    let day = days::find(args.day).ok_or(format!("error: day {} is not solved yet\n", args.day))?;
+    let source = args.input.unwrap_or_else(|| day.input.source());
+    let input = read_source(&source)?;
+    let timings = day
+        .bench(&input, args.runs)
+        .map_err(|err| err.with_file(source.to_string()).render(&input))?;
+
+    let baseline = match &args.baseline {
+        Some(path) => bench::load(path).map_err(|why| format!("error: {}\n", why))?,
+        None => bench::Baseline::new(),
+    };
+
+    println!("Day {:02} ({} runs)", day.number, args.runs);
+    let regressions = bench::report(&timings, baseline.get(&day.number), args.threshold);
+
+    if let Some(path) = &args.save_baseline {
+        bench::save(
+            path,
+            day.number,
+            bench::DayBaseline::new(&timings, args.runs),
+        )
+        .map_err(|why| format!("error: {}\n", why))?;
+    }
+
+    if regressions > 0 {
+        return Err(format!(
+            "error: {} stage(s) slower than the baseline by more than {}%\n",
+            regressions, args.threshold
+        ));
+    }
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Run(args) => run(args),
+        Command::Bench(args) => bench(args),
+    };
+    if let Err(why) = result {
+        eprint!("{}", why);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{day::Solution, error::ParseError};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+// Summary of repeated timings of one stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort();
+        // Nearest rank percentile, so a single slow run shows up in p95 once there are 20 runs
+        let p95 = (samples.len() * 95).div_ceil(100) - 1;
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            p95: samples[p95],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+fn time<T>(samples: &mut Vec<Duration>, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = black_box(f());
+    samples.push(start.elapsed());
+    result
+}
+
+// Time every stage of a day separately over `runs` repetitions, at least 1
+pub fn bench<S: Solution>(input: &str, runs: usize) -> Result<Timings, ParseError> {
+    let mut parse = Vec::with_capacity(runs);
+    let mut part1 = Vec::with_capacity(runs);
+    let mut part2 = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let parsed = time(&mut parse, || S::parse(black_box(input)))?;
+        time(&mut part1, || S::part1(&parsed));
+        time(&mut part2, || S::part2(&parsed));
+    }
+    Ok(Timings {
+        parse: Stats::from_samples(parse),
+        part1: Stats::from_samples(part1),
+        part2: Stats::from_samples(part2),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(ms(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+
+        let mut samples: Vec<u64> = (1..=20).collect();
+        samples[19] = 100;
+        let stats = Stats::from_samples(ms(&samples));
+        assert_eq!(stats.median, Duration::from_millis(11));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+    }
+
+    #[test]
+    fn test_stats_single_sample() {
+        let stats = Stats::from_samples(ms(&[7]));
+        assert_eq!(
+            (stats.min, stats.median, stats.p95),
+            (stats.p95, stats.p95, stats.p95)
+        );
+    }
+}
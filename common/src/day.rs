@@ -0,0 +1,230 @@
+use crate::{
+    bench::{bench, Timings},
+    error::ParseError,
+    input::DefaultInput,
+};
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn both() -> [Part; 2] {
+        [Part::One, Part::Two]
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part `{}`, expected 1 or 2", s)),
+        }
+    }
+}
+
+// A worked example from the puzzle text together with its published answer
+pub struct Example {
+    pub part: Part,
+    pub input: &'static str,
+    pub answer: &'static str,
+}
+
+// A day of the calendar, split into typed stages so the input is parsed once
+pub trait Solution {
+    const DAY: u8;
+    const EXAMPLES: &'static [Example];
+
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Vec<String>, ParseError> {
+    let input = S::parse(input)?;
+    Ok(parts
+        .iter()
+        .map(|part| match part {
+            Part::One => S::part1(&input).to_string(),
+            Part::Two => S::part2(&input).to_string(),
+        })
+        .collect())
+}
+
+// Solve every example of a day, panicking with a report of all wrong answers
+pub fn check_examples<S: Solution>() {
+    assert!(!S::EXAMPLES.is_empty(), "day {} has no examples", S::DAY);
+    let mut failures = Vec::new();
+    for (i, example) in S::EXAMPLES.iter().enumerate() {
+        let answer = match run::<S>(example.input, &[example.part]) {
+            Err(err) => err.render(example.input),
+            Ok(answers) => answers[0].clone(),
+        };
+        if answer != example.answer {
+            failures.push(format!(
+                "day {} example {} part {}: expected {}, got {}",
+                S::DAY,
+                i + 1,
+                example.part,
+                example.answer,
+                answer
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+// Type erased entry point every day exposes to the runner
+pub struct Day {
+    pub number: u8,
+    pub input: DefaultInput,
+    pub examples: &'static [Example],
+    run: fn(&str, &[Part]) -> Result<Vec<String>, ParseError>,
+    bench: fn(&str, usize) -> Result<Timings, ParseError>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(input: DefaultInput) -> Day {
+        Day {
+            number: S::DAY,
+            input,
+            examples: S::EXAMPLES,
+            run: run::<S>,
+            bench: bench::<S>,
+        }
+    }
+
+    // Parse the input once and solve the requested parts in order
+    pub fn run(&self, input: &str, parts: &[Part]) -> Result<Vec<String>, ParseError> {
+        (self.run)(input, parts)
+    }
+
+    // Time parsing and both parts separately over `runs` repetitions
+    pub fn bench(&self, input: &str, runs: usize) -> Result<Timings, ParseError> {
+        (self.bench)(input, runs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::lines;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        const EXAMPLES: &'static [Example] = &[
+            Example {
+                part: Part::One,
+                input: "1\n2\n3\n",
+                answer: "6",
+            },
+            Example {
+                part: Part::Two,
+                input: "1\n2\n3\n",
+                answer: "3",
+            },
+        ];
+
+        type Input = Vec<i32>;
+        type Part1 = i32;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+            lines(input).map(|line| line.parse(line.text)).collect()
+        }
+
+        fn part1(input: &Vec<i32>) -> i32 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Vec<i32>) -> usize {
+            input.len()
+        }
+    }
+
+    #[test]
+    fn test_day_run() {
+        let day = Day::new::<Sum>(DefaultInput {
+            path: "input.txt",
+            embedded: None,
+        });
+        assert_eq!(
+            day.run("1\n2\n3\n", &[Part::Two, Part::One]),
+            Ok(vec!["3".to_string(), "6".to_string()])
+        );
+        assert_eq!(day.run("4\n", &[Part::One]), Ok(vec!["4".to_string()]));
+        let err = day.run("4\nx\n", &[Part::One]).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_check_examples() {
+        check_examples::<Sum>();
+    }
+
+    struct Wrong;
+
+    impl Solution for Wrong {
+        const DAY: u8 = 0;
+        const EXAMPLES: &'static [Example] = &[Example {
+            part: Part::One,
+            input: "1\n2\n",
+            answer: "4",
+        }];
+
+        type Input = Vec<i32>;
+        type Part1 = i32;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+            Sum::parse(input)
+        }
+
+        fn part1(input: &Vec<i32>) -> i32 {
+            Sum::part1(input)
+        }
+
+        fn part2(input: &Vec<i32>) -> usize {
+            Sum::part2(input)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "day 0 example 1 part 1: expected 4, got 3")]
+    fn test_check_examples_wrong_answer() {
+        check_examples::<Wrong>();
+    }
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!("1".parse::<Part>(), Ok(Part::One));
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use std::{error::Error, fmt, path::PathBuf};
+
+// Where and why the puzzle input could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    // 1-based line number
+    pub line: usize,
+    // 1-based column, counted in chars
+    pub column: usize,
+    // The offending text, empty when something is missing
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            file: None,
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> ParseError {
+        self.file = Some(file.into());
+        self
+    }
+
+    // Render the error rustc style, pointing at the offending text in `source`
+    //
+    //   error: invalid number
+    //    --> input.txt:3:5
+    //     |
+    //   3 | 1 2 x 4
+    //     |     ^
+    pub fn render(&self, source: &str) -> String {
+        let location = match &self.file {
+            Some(file) => format!("{}:{}:{}", file.display(), self.line, self.column),
+            None => format!("{}:{}", self.line, self.column),
+        };
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let mut out = format!("error: {}\n{}--> {}\n", self, gutter, location);
+
+        if let Some(line) = source.lines().nth(self.line - 1) {
+            let padding = " ".repeat(self.column - 1);
+            let carets = "^".repeat(self.text.chars().count().max(1));
+            out += &format!("{} |\n", gutter);
+            out += &format!("{} | {}\n", number, line);
+            out += &format!("{} | {}{}\n", gutter, padding, carets);
+        }
+        out
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.text.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} `{}`", self.message, self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let source = "1 2 3\n1 2 x4 5\n";
+        let err = ParseError::new(2, 5, "x4", "invalid number").with_file("input.txt");
+        assert_eq!(
+            err.render(source),
+            "error: invalid number `x4`\n \
+             --> input.txt:2:5\n  \
+             |\n\
+             2 | 1 2 x4 5\n  \
+             |     ^^\n"
+        );
+    }
+
+    #[test]
+    fn test_render_missing_text() {
+        let err = ParseError::new(1, 2, "", "expected `|`");
+        assert_eq!(
+            err.render("7\n"),
+            "error: expected `|`\n --> 1:2\n  |\n1 | 7\n  |  ^\n"
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use crate::{error::ParseError, parse::lines, vec2::Vec2};
+use std::fmt;
+
+// Rectangular grid stored row by row in one flat vector
+//
+// Positions are `Vec2<i32>` with x to the right and y down, so stepping off the
+// edge simply yields `None` instead of underflowing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // Both sides have to be at least 1, like every grid `from_str` returns
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        assert!(width > 0 && height > 0, "empty {}x{} grid", width, height);
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    // Parse one cell per char, `f` returns `None` for chars that are not allowed
+    pub fn from_str(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in lines(input) {
+            let row_width = line.text.chars().count();
+            let width = *width.get_or_insert(row_width);
+            if row_width != width {
+                return Err(line.error(
+                    line.text,
+                    format!(
+                        "row {} has {} columns where row 1 has {}",
+                        height + 1,
+                        row_width,
+                        width
+                    ),
+                ));
+            }
+            for (i, c) in line.text.char_indices() {
+                match f(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        let text = &line.text[i..i + c.len_utf8()];
+                        return Err(line.error(text, "invalid character"));
+                    }
+                }
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(ParseError::new(1, 1, "", "empty grid")),
+        }
+    }
+
+    // Like `from_str`, but rows shorter than the longest one are filled up with `fill`
+    pub fn from_str_padded(
+        input: &str,
+        fill: char,
+        f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let width = lines(input)
+            .map(|line| line.text.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut padded = String::with_capacity(input.len());
+        for line in lines(input) {
+            padded += line.text;
+            padded.extend(std::iter::repeat_n(fill, width - line.text.chars().count()));
+            padded.push('\n');
+        }
+        Grid::from_str(&padded, f)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, position: Vec2<i32>) -> bool {
+        position.x >= 0
+            && position.x < self.width as i32
+            && position.y >= 0
+            && position.y < self.height as i32
+    }
+
+    fn index(&self, position: Vec2<i32>) -> Option<usize> {
+        if self.in_bounds(position) {
+            Some(position.y as usize * self.width + position.x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn position(&self, index: usize) -> Vec2<i32> {
+        Vec2::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn get(&self, position: Vec2<i32>) -> Option<&T> {
+        self.index(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Vec2<i32>) -> Option<&mut T> {
+        self.index(position).map(|i| &mut self.cells[i])
+    }
+
+    // Store `value` at `position`, returns false if the position is out of bounds
+    pub fn set(&mut self, position: Vec2<i32>, value: T) -> bool {
+        match self.get_mut(position) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Like `get` but the grid repeats infinitely in every direction
+    pub fn get_wrapping(&self, position: Vec2<i32>) -> &T {
+        let x = position.x.rem_euclid(self.width as i32);
+        let y = position.y.rem_euclid(self.height as i32);
+        &self.cells[y as usize * self.width + x as usize]
+    }
+
+    // All cells in row-major order together with their position
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2<i32>, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position(i), cell))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(
+            x < self.width,
+            "column {} outside a grid {} wide",
+            x,
+            self.width
+        );
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    // Walk from `start` in steps of `step` until leaving the grid
+    pub fn ray(
+        &self,
+        start: Vec2<i32>,
+        step: Vec2<i32>,
+    ) -> impl Iterator<Item = (Vec2<i32>, &T)> + '_ {
+        std::iter::successors(Some(start), move |p| Some(*p + step))
+            .map_while(move |p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    // Every maximal ray through the grid in direction `step`
+    //
+    // `(1, 0)` gives the rows, `(0, 1)` the columns, `(1, 1)` and `(-1, 1)` the diagonals,
+    // negated steps walk the same lines backwards.
+    pub fn lines(
+        &self,
+        step: Vec2<i32>,
+    ) -> impl Iterator<Item = impl Iterator<Item = (Vec2<i32>, &T)> + '_> + '_ {
+        (0..self.cells.len())
+            .map(|i| self.position(i))
+            .filter(move |p| !self.in_bounds(*p - step))
+            .map(move |start| self.ray(start, step))
+    }
+
+    // The diagonals from top-left to bottom-right
+    pub fn diagonals(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = (Vec2<i32>, &T)> + '_> + '_ {
+        self.lines(Vec2::new(1, 1))
+    }
+
+    // The diagonals from top-right to bottom-left
+    pub fn anti_diagonals(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = (Vec2<i32>, &T)> + '_> + '_ {
+        self.lines(Vec2::new(-1, 1))
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        position: Vec2<i32>,
+        directions: &'a [Vec2<i32>],
+    ) -> impl Iterator<Item = (Vec2<i32>, &'a T)> + 'a {
+        directions.iter().filter_map(move |d| {
+            let p = position + *d;
+            self.get(p).map(|cell| (p, cell))
+        })
+    }
+
+    // The edge-adjacent cells inside the grid
+    pub fn neighbours4(&self, position: Vec2<i32>) -> impl Iterator<Item = (Vec2<i32>, &T)> + '_ {
+        self.neighbours(position, &Vec2::CARDINALS)
+    }
+
+    // The edge- and corner-adjacent cells inside the grid
+    pub fn neighbours8(&self, position: Vec2<i32>) -> impl Iterator<Item = (Vec2<i32>, &T)> + '_ {
+        self.neighbours(position, &Vec2::COMPASS)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abc() -> Grid<char> {
+        Grid::from_str("abc\ndef\n", Some).unwrap()
+    }
+
+    #[test]
+    fn test_from_str() {
+        let grid = abc();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Vec2::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Vec2::new(3, 1)), None);
+        assert_eq!(grid.get(Vec2::new(-1, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        let err = Grid::from_str("ab\nabc\n", Some).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "abc"));
+        assert_eq!(
+            err.to_string(),
+            "row 2 has 3 columns where row 1 has 2 `abc`"
+        );
+        let err = Grid::from_str("ab\n\nab\n", Some).unwrap_err();
+        assert_eq!(err.to_string(), "row 2 has 0 columns where row 1 has 2");
+        let err = Grid::from_str("..\n.?\n", |c| (c == '.').then_some(c)).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "?"));
+        assert_eq!(Grid::from_str("", Some).unwrap_err().message, "empty grid");
+    }
+
+    #[test]
+    fn test_from_str_unicode() {
+        let grid = Grid::from_str("äb\ncö\n", Some).unwrap();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.get(Vec2::new(1, 1)), Some(&'ö'));
+    }
+
+    #[test]
+    fn test_from_str_padded() {
+        let grid = Grid::from_str_padded("ab\nä\n\nabc", ' ', Some).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid.to_string(), "ab \nä  \n   \nabc\n");
+        let err = Grid::from_str_padded("ab\nx?\n", '.', |c| (c != '?').then_some(c)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn test_set_and_wrapping() {
+        let mut grid = abc();
+        assert!(grid.set(Vec2::new(0, 1), 'x'));
+        assert!(!grid.set(Vec2::new(0, 2), 'x'));
+        *grid.get_mut(Vec2::new(1, 0)).unwrap() = 'y';
+        assert_eq!(grid.to_string(), "ayc\nxef\n");
+        assert_eq!(grid.get_wrapping(Vec2::new(-1, -1)), &'f');
+        assert_eq!(grid.get_wrapping(Vec2::new(3, 2)), &'a');
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = abc();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        assert_eq!(grid.row(1).collect::<String>(), "def");
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+    }
+
+    #[test]
+    #[should_panic(expected = "column 4 outside a grid 3 wide")]
+    fn test_column_out_of_range() {
+        let _ = abc().column(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty 0x2 grid")]
+    fn test_new_empty() {
+        Grid::new(0, 2, '.');
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = abc();
+        let collect = |step| {
+            grid.lines(step)
+                .map(|line| line.map(|(_, c)| c).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(collect(Vec2::new(1, 0)), vec!["abc", "def"]);
+        assert_eq!(collect(Vec2::new(0, -1)), vec!["da", "eb", "fc"]);
+        assert_eq!(collect(Vec2::new(1, 1)), vec!["ae", "bf", "c", "d"]);
+        assert_eq!(collect(Vec2::new(-1, 1)), vec!["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = abc();
+        let n4: String = grid.neighbours4(Vec2::new(0, 0)).map(|(_, c)| c).collect();
+        assert_eq!(n4, "bd");
+        let n8: String = grid.neighbours8(Vec2::new(1, 1)).map(|(_, c)| c).collect();
+        assert_eq!(n8, "bcfda");
+    }
+}
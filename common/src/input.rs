@@ -0,0 +1,198 @@
+use crate::{day::Solution, error::ParseError};
+use std::{
+    fmt,
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+// Where a day looks for its puzzle input when none is given
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultInput {
+    // The input.txt next to the day's crate
+    pub path: &'static str,
+    // The same file baked into the binary when built with the `embed-input` feature
+    pub embedded: Option<&'static str>,
+}
+
+// Default input of the crate this is expanded in, embedding input.txt behind its `embed-input` feature
+#[macro_export]
+macro_rules! default_input {
+    () => {
+        $crate::input::DefaultInput {
+            path: concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"),
+            embedded: {
+                #[cfg(feature = "embed-input")]
+                let embedded = Some(include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/input.txt"
+                )));
+                #[cfg(not(feature = "embed-input"))]
+                let embedded = None;
+                embedded
+            },
+        }
+    };
+}
+
+impl DefaultInput {
+    // Prefer the embedded copy so the binary works from any directory
+    pub fn source(&self) -> Source {
+        match self.embedded {
+            Some(contents) => Source::Embedded(contents),
+            None => Source::Path(PathBuf::from(self.path)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    Embedded(&'static str),
+}
+
+impl Source {
+    // The source named by the first command line argument, the default input otherwise
+    pub fn from_args(default: &DefaultInput) -> Source {
+        match std::env::args().nth(1) {
+            Some(arg) => arg.parse().unwrap(),
+            None => default.source(),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, String> {
+        match self {
+            Source::Path(path) => std::fs::read_to_string(path)
+                .map_err(|why| format!("couldn't read {}: {}", path.display(), why)),
+            Source::Stdin => {
+                let mut contents = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|why| format!("couldn't read stdin: {}", why))?;
+                Ok(contents)
+            }
+            Source::Embedded(contents) => Ok(contents.to_string()),
+        }
+    }
+
+    // Open the source for reading line by line without loading it whole
+    pub fn open(&self) -> Result<Box<dyn BufRead>, String> {
+        match self {
+            Source::Path(path) => File::open(path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|why| format!("couldn't read {}: {}", path.display(), why)),
+            Source::Stdin => Ok(Box::new(std::io::stdin().lock())),
+            Source::Embedded(contents) => Ok(Box::new(contents.as_bytes())),
+        }
+    }
+}
+
+// `-` is stdin, anything else a path
+impl FromStr for Source {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-" => Ok(Source::Stdin),
+            _ => Ok(Source::Path(PathBuf::from(s))),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Embedded(_) => write!(f, "<embedded input.txt>"),
+        }
+    }
+}
+
+// Read and parse the puzzle input of a day, exiting with a diagnostic when it is malformed
+pub fn parse_input<S: Solution>(source: &Source) -> S::Input {
+    parse_input_with(source, S::parse)
+}
+
+// Like `parse_input`, for days that can parse their input in more than one way
+pub fn parse_input_with<T>(
+    source: &Source,
+    parse: impl FnOnce(&str) -> Result<T, ParseError>,
+) -> T {
+    let contents = match source.read() {
+        Err(why) => {
+            eprintln!("error: {}", why);
+            std::process::exit(1);
+        }
+        Ok(contents) => contents,
+    };
+    match parse(&contents) {
+        Err(err) => {
+            eprint!("{}", err.with_file(source.to_string()).render(&contents));
+            std::process::exit(1);
+        }
+        Ok(input) => input,
+    }
+}
+
+// Like `parse_input`, but `read` gets the input line by line instead of as one string
+//
+// For pipes and inputs too large to hold as text, errors are shown without the source line.
+pub fn stream_input<T>(
+    source: &Source,
+    read: impl FnOnce(Box<dyn BufRead>) -> Result<T, ParseError>,
+) -> T {
+    let reader = match source.open() {
+        Err(why) => {
+            eprintln!("error: {}", why);
+            std::process::exit(1);
+        }
+        Ok(reader) => reader,
+    };
+    match read(reader) {
+        Err(err) => {
+            eprint!("{}", err.with_file(source.to_string()).render(""));
+            std::process::exit(1);
+        }
+        Ok(input) => input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_from_str() {
+        assert_eq!("-".parse(), Ok(Source::Stdin));
+        assert_eq!(
+            "06/task/input.txt".parse(),
+            Ok(Source::Path(PathBuf::from("06/task/input.txt")))
+        );
+    }
+
+    #[test]
+    fn test_default_source() {
+        let default = DefaultInput {
+            path: "input.txt",
+            embedded: None,
+        };
+        assert_eq!(default.source(), Source::Path(PathBuf::from("input.txt")));
+        let default = DefaultInput {
+            path: "input.txt",
+            embedded: Some("1\n"),
+        };
+        assert_eq!(default.source(), Source::Embedded("1\n"));
+        assert_eq!(default.source().read(), Ok("1\n".to_string()));
+        let mut line = String::new();
+        default
+            .source()
+            .open()
+            .unwrap()
+            .read_line(&mut line)
+            .unwrap();
+        assert_eq!(line, "1\n");
+    }
+}
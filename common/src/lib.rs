@@ -0,0 +1,9 @@
+//! Building blocks shared by every day of the calendar.
+
+pub mod bench;
+pub mod day;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod vec2;
@@ -0,0 +1,129 @@
+use crate::error::ParseError;
+use std::{io::BufRead, str::FromStr};
+
+// A line of the puzzle input together with its 1-based line number
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+// Iterate the lines of the input, keeping track of the line numbers for errors
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+// Call `f` with every line of `reader` in turn, so the input never has to be held as a whole
+//
+// Works on pipes and other streams that can't seek, returns the number of lines read.
+pub fn read_lines(
+    reader: impl BufRead,
+    mut f: impl FnMut(&Line) -> Result<(), ParseError>,
+) -> Result<usize, ParseError> {
+    let mut number = 0;
+    for text in reader.lines() {
+        number += 1;
+        let text = text.map_err(|why| {
+            ParseError::new(number, 1, "", format!("couldn't read line: {}", why))
+        })?;
+        f(&Line {
+            number,
+            text: &text,
+        })?;
+    }
+    Ok(number)
+}
+
+impl<'a> Line<'a> {
+    // 1-based column of `part`, which has to be a slice of this line
+    pub fn column_of(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset <= self.text.len())
+            .unwrap_or(0);
+        self.text[..offset].chars().count() + 1
+    }
+
+    // Error pointing at `part` of this line
+    pub fn error(&self, part: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column_of(part), part, message)
+    }
+
+    // Error pointing just past the end of this line, for truncated input
+    pub fn error_at_end(&self, message: impl Into<String>) -> ParseError {
+        self.error(&self.text[self.text.len()..], message)
+    }
+
+    pub fn parse<T: FromStr>(&self, part: &str) -> Result<T, ParseError> {
+        part.parse::<T>()
+            .map_err(|_| self.error(part, "invalid number"))
+    }
+
+    // Split the line in two at the first `sep`
+    pub fn split_once(&self, sep: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(sep)
+            .ok_or_else(|| self.error_at_end(format!("expected `{}`", sep)))
+    }
+
+    // Parse every `sep` separated field of the line
+    pub fn numbers<T: FromStr>(&self, sep: &str) -> Result<Vec<T>, ParseError> {
+        self.text.split(sep).map(|x| self.parse::<T>(x)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        let line = Line {
+            number: 1,
+            text: "7 6 4 2 1",
+        };
+        assert_eq!(line.numbers::<i32>(" "), Ok(vec![7, 6, 4, 2, 1]));
+        let line = Line {
+            number: 1,
+            text: "75,47,61",
+        };
+        assert_eq!(line.numbers::<i64>(","), Ok(vec![75, 47, 61]));
+    }
+
+    #[test]
+    fn test_numbers_error_position() {
+        let line = lines("1,2\n75,4x,61").nth(1).unwrap();
+        let err = line.numbers::<i64>(",").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 4, "4x"));
+    }
+
+    #[test]
+    fn test_split_once_missing() {
+        let mut lines = lines("47|53\n97");
+        assert_eq!(lines.next().unwrap().split_once("|"), Ok(("47", "53")));
+        let line = lines.next().unwrap();
+        let err = line.split_once("|").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, ""));
+    }
+
+    #[test]
+    fn test_read_lines() {
+        let mut texts = Vec::new();
+        let count = read_lines("a\nbc\n\nd".as_bytes(), |line| {
+            texts.push((line.number, line.text.to_string()));
+            Ok(())
+        });
+        assert_eq!(count, Ok(4));
+        assert_eq!(texts[1], (2, "bc".to_string()));
+        assert_eq!(texts[3], (4, "d".to_string()));
+
+        let err = read_lines("1\n2\nx\n".as_bytes(), |line| {
+            line.parse::<i32>(line.text).map(|_| ())
+        });
+        assert_eq!(err.unwrap_err().line, 3);
+    }
+}
@@ -0,0 +1,257 @@
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign};
+
+// 2D vector, used as grid coordinate with x to the right and y pointing down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+pub enum RotateDirection {
+    Left,
+    Right,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Vec2<T> {
+        Vec2 { x, y }
+    }
+}
+
+impl Vec2<i32> {
+    pub const NORTH: Vec2<i32> = Vec2::new(0, -1);
+    pub const NORTH_EAST: Vec2<i32> = Vec2::new(1, -1);
+    pub const EAST: Vec2<i32> = Vec2::new(1, 0);
+    pub const SOUTH_EAST: Vec2<i32> = Vec2::new(1, 1);
+    pub const SOUTH: Vec2<i32> = Vec2::new(0, 1);
+    pub const SOUTH_WEST: Vec2<i32> = Vec2::new(-1, 1);
+    pub const WEST: Vec2<i32> = Vec2::new(-1, 0);
+    pub const NORTH_WEST: Vec2<i32> = Vec2::new(-1, -1);
+
+    // The 4 edge-adjacent directions, clockwise starting north
+    pub const CARDINALS: [Vec2<i32>; 4] = [Vec2::NORTH, Vec2::EAST, Vec2::SOUTH, Vec2::WEST];
+
+    // All 8 surrounding directions, clockwise starting north
+    pub const COMPASS: [Vec2<i32>; 8] = [
+        Vec2::NORTH,
+        Vec2::NORTH_EAST,
+        Vec2::EAST,
+        Vec2::SOUTH_EAST,
+        Vec2::SOUTH,
+        Vec2::SOUTH_WEST,
+        Vec2::WEST,
+        Vec2::NORTH_WEST,
+    ];
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn add(self, other: Vec2<T>) -> Vec2<T> {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn sub(self, other: Vec2<T>) -> Vec2<T> {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, other: Vec2<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, other: Vec2<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn mul(self, scalar: T) -> Vec2<T> {
+        Vec2::new(self.x * scalar, self.y * scalar)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn div(self, scalar: T) -> Vec2<T> {
+        Vec2::new(self.x / scalar, self.y / scalar)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn neg(self) -> Vec2<T> {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl<T> Vec2<T>
+where
+    T: Copy + Neg<Output = T>,
+{
+    // Rotate by 90 degrees clockwise as seen on a map
+    pub fn rotate_right(&self) -> Vec2<T> {
+        Vec2::new(-self.y, self.x)
+    }
+
+    // Rotate by 90 degrees counter-clockwise as seen on a map
+    pub fn rotate_left(&self) -> Vec2<T> {
+        Vec2::new(self.y, -self.x)
+    }
+
+    pub fn rotate_180(&self) -> Vec2<T> {
+        Vec2::new(-self.x, -self.y)
+    }
+
+    pub fn rotate(&self, direction: RotateDirection) -> Vec2<T> {
+        match direction {
+            RotateDirection::Right => self.rotate_right(),
+            RotateDirection::Left => self.rotate_left(),
+        }
+    }
+}
+
+fn abs_diff<T: Copy + PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Vec2<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    // Number of 4-connected steps between the two points
+    pub fn manhattan(&self, other: &Vec2<T>) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    // Number of 8-connected steps between the two points
+    pub fn chebyshev(&self, other: &Vec2<T>) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+impl<T> Vec2<T>
+where
+    T: Copy + Default + PartialOrd + Neg<Output = T> + Div<Output = T> + Rem<Output = T>,
+{
+    // Shortest step with the same direction, e.g. (4, -6) becomes (2, -3)
+    pub fn normalized(&self) -> Vec2<T> {
+        let zero = T::default();
+        let abs = |v: T| if v < zero { -v } else { v };
+        let (mut a, mut b) = (abs(self.x), abs(self.y));
+        while b != zero {
+            (a, b) = (b, a % b);
+        }
+        if a == zero {
+            return *self;
+        }
+        Vec2::new(self.x / a, self.y / a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vec2_add() {
+        let a = Vec2::new(1, 2);
+        let b = Vec2::new(3, 4);
+        let c = a + b;
+        assert_eq!(c.x, 4);
+        assert_eq!(c.y, 6);
+    }
+
+    #[test]
+    fn test_vec2_sub_div() {
+        let a = Vec2::new(8, 6);
+        let b = Vec2::new(2, 2);
+        assert_eq!(a - b, Vec2::new(6, 4));
+        assert_eq!(a / 2, Vec2::new(4, 3));
+    }
+
+    #[test]
+    fn test_vec2_mul_neg_assign() {
+        let mut a = Vec2::new(1, -2);
+        assert_eq!(a * 3, Vec2::new(3, -6));
+        assert_eq!(-a, Vec2::new(-1, 2));
+        a += Vec2::new(1, 1);
+        assert_eq!(a, Vec2::new(2, -1));
+        a -= Vec2::new(2, 2);
+        assert_eq!(a, Vec2::new(0, -3));
+    }
+
+    #[test]
+    fn test_vec2_rotate() {
+        let a = Vec2::new(0, -1);
+        let b = a.rotate(RotateDirection::Right);
+        assert_eq!(b.x, 1);
+        assert_eq!(b.y, 0);
+        let c = b.rotate(RotateDirection::Right);
+        assert_eq!(c.x, 0);
+        assert_eq!(c.y, 1);
+        let d = c.rotate(RotateDirection::Right);
+        assert_eq!(d.x, -1);
+        assert_eq!(d.y, 0);
+        let e = d.rotate(RotateDirection::Left);
+        assert_eq!(e.x, 0);
+        assert_eq!(e.y, 1);
+        let f = e.rotate(RotateDirection::Left);
+        assert_eq!(f.x, 1);
+        assert_eq!(f.y, 0);
+        let g = f.rotate(RotateDirection::Left);
+        assert_eq!(g.x, 0);
+        assert_eq!(g.y, -1);
+    }
+
+    #[test]
+    fn test_vec2_compass() {
+        for (i, &d) in Vec2::COMPASS.iter().enumerate() {
+            assert_eq!(d.rotate_right().rotate_right(), Vec2::COMPASS[(i + 4) % 8]);
+            assert_eq!(d.rotate_180(), -d);
+        }
+        for (i, &d) in Vec2::CARDINALS.iter().enumerate() {
+            assert_eq!(d.rotate_right(), Vec2::CARDINALS[(i + 1) % 4]);
+            assert_eq!(d.rotate_left(), Vec2::CARDINALS[(i + 3) % 4]);
+        }
+    }
+
+    #[test]
+    fn test_vec2_distances() {
+        let a = Vec2::new(1, -2);
+        let b = Vec2::new(-3, 5);
+        assert_eq!(a.manhattan(&b), 11);
+        assert_eq!(a.chebyshev(&b), 7);
+        assert_eq!(Vec2::new(2u32, 7).manhattan(&Vec2::new(5, 1)), 9);
+    }
+
+    #[test]
+    fn test_vec2_normalized() {
+        assert_eq!(Vec2::new(4, -6).normalized(), Vec2::new(2, -3));
+        assert_eq!(Vec2::new(0, 5).normalized(), Vec2::new(0, 1));
+        assert_eq!(Vec2::new(-7, 0).normalized(), Vec2::new(-1, 0));
+        assert_eq!(Vec2::new(0, 0).normalized(), Vec2::new(0, 0));
+        assert_eq!(Vec2::new(3i64, 5).normalized(), Vec2::new(3, 5));
+    }
+}